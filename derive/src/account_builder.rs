@@ -0,0 +1,75 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Fields};
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "AccountBuilder requires a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "AccountBuilder can only be derived for structs",
+            ))
+        }
+    };
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &input.generics,
+            "AccountBuilder does not support generic structs",
+        ));
+    }
+
+    let vis = &input.vis;
+    let name = &input.ident;
+    let builder = format_ident!("{}Builder", name);
+
+    let idents = fields
+        .iter()
+        .map(|field| field.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+    let setters = idents
+        .iter()
+        .map(|ident| format_ident!("with_{}", ident))
+        .collect::<Vec<_>>();
+    let missing = idents
+        .iter()
+        .map(|ident| format!("missing field `{}`", ident))
+        .collect::<Vec<_>>();
+
+    Ok(quote! {
+        #[derive(Default)]
+        #vis struct #builder {
+            #(#idents: Option<#types>,)*
+        }
+
+        impl #builder {
+            #(
+                pub fn #setters(mut self, #idents: #types) -> Self {
+                    self.#idents = Some(#idents);
+                    self
+                }
+            )*
+
+            pub fn build(self) -> Result<#name, &'static str> {
+                Ok(#name {
+                    #(#idents: self.#idents.ok_or(#missing)?,)*
+                })
+            }
+        }
+
+        impl #name {
+            pub fn builder() -> #builder {
+                #builder::default()
+            }
+        }
+    })
+}
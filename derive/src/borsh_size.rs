@@ -0,0 +1,78 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Field, GenericArgument, LitInt, PathArguments, Type};
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "BorshSize can only be derived for structs",
+            ))
+        }
+    };
+
+    let sizes = fields
+        .iter()
+        .map(field_size)
+        .collect::<syn::Result<Vec<_>>>()?;
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::geppetto::BorshSize for #name #ty_generics #where_clause {
+            const MAX_SIZE: usize = 0 #(+ #sizes)*;
+        }
+    })
+}
+
+fn field_size(field: &Field) -> syn::Result<TokenStream> {
+    let max_len = field
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("max_len"))
+        .map(|attr| attr.parse_args::<LitInt>())
+        .transpose()?;
+
+    let ty = &field.ty;
+    match (last_segment(ty), max_len) {
+        (Some(("String", _)), Some(max_len)) => Ok(quote! { (4 + #max_len) }),
+        (Some(("Vec", Some(inner))), Some(max_len)) => {
+            Ok(quote! { (4 + #max_len * <#inner as ::geppetto::BorshSize>::MAX_SIZE) })
+        }
+        (Some(("String", _)) | Some(("Vec", _)), None) => Err(syn::Error::new_spanned(
+            ty,
+            "dynamically sized fields require a #[max_len(N)] attribute",
+        )),
+        (_, Some(max_len)) => Err(syn::Error::new_spanned(
+            max_len,
+            "#[max_len(N)] is only supported on `Vec` and `String` fields",
+        )),
+        (_, None) => Ok(quote! { <#ty as ::geppetto::BorshSize>::MAX_SIZE }),
+    }
+}
+
+/// Returns the name of the last path segment of a type, along with its first generic argument.
+fn last_segment(ty: &Type) -> Option<(&'static str, Option<&Type>)> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    let name = if segment.ident == "Vec" {
+        "Vec"
+    } else if segment.ident == "String" {
+        "String"
+    } else {
+        return None;
+    };
+    let inner = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args.args.iter().find_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }),
+        _ => None,
+    };
+    Some((name, inner))
+}
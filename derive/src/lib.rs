@@ -0,0 +1,37 @@
+mod account_builder;
+mod borsh_size;
+mod schema;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput};
+
+/// Generates a `<Name>Builder` struct with a `with_<field>` setter per field and a `build`
+/// method that fails if any field was left unset.
+#[proc_macro_derive(AccountBuilder)]
+pub fn derive_account_builder(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    account_builder::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `geppetto::BorshSize`, computing the maximum borsh-serialized size of a struct.
+///
+/// `Vec` and `String` fields must be annotated with `#[max_len(N)]`.
+#[proc_macro_derive(BorshSize, attributes(max_len))]
+pub fn derive_borsh_size(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    borsh_size::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Implements `geppetto::EventSchema` for a struct, describing its field names and types as a
+/// JSON object for off-chain event parsers.
+#[proc_macro_attribute]
+pub fn schema(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    schema::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
@@ -0,0 +1,47 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{Data, DeriveInput, Fields};
+
+pub fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "#[schema] can only be applied to structs",
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let schema = format!(
+        r#"{{"name":"{}","fields":[{}]}}"#,
+        name,
+        field_schemas(fields).join(",")
+    );
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        #input
+
+        impl #impl_generics ::geppetto::EventSchema for #name #ty_generics #where_clause {
+            const SCHEMA: &'static str = #schema;
+        }
+    })
+}
+
+fn field_schemas(fields: &Fields) -> Vec<String> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let name = field
+                .ident
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_else(|| index.to_string());
+            let ty = field.ty.to_token_stream().to_string().replace(' ', "");
+            format!(r#"{{"name":"{}","type":"{}"}}"#, name, ty)
+        })
+        .collect()
+}
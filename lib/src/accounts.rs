@@ -0,0 +1,124 @@
+use bytemuck::Pod;
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{expect_accounts, try_pod_body, AccountInfoValidation, Discriminator, POD_BODY_OFFSET};
+
+/// Wrapper over a slice of accounts for declarative batch validation.
+#[derive(Clone, Copy)]
+pub struct AccountInfoSlice<'a>(pub &'a [AccountInfo]);
+
+impl<'a> AccountInfoSlice<'a> {
+    /// Yields each account as a `T` owned by `program_id`, or the error for the account that
+    /// failed validation.
+    pub fn of_type<T: Discriminator + Pod>(
+        self,
+        program_id: &'a Pubkey,
+    ) -> impl Iterator<Item = Result<Ref<'a, T>, ProgramError>> + 'a {
+        self.0.iter().map(move |account| {
+            account.assert_type::<T>(program_id)?;
+            let data = account.try_borrow_data()?;
+            try_pod_body::<T>(&data)?;
+            // The length and alignment were checked above, so the cast cannot fail.
+            Ok(Ref::map(data, |data| {
+                bytemuck::from_bytes::<T>(
+                    &data[POD_BODY_OFFSET..POD_BODY_OFFSET + std::mem::size_of::<T>()],
+                )
+            }))
+        })
+    }
+}
+
+impl<'a> From<&'a [AccountInfo]> for AccountInfoSlice<'a> {
+    fn from(accounts: &'a [AccountInfo]) -> Self {
+        Self(accounts)
+    }
+}
+
+impl<'a> IntoIterator for AccountInfoSlice<'a> {
+    type Item = &'a AccountInfo;
+    type IntoIter = std::slice::Iter<'a, AccountInfo>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// Fixed-size view over the leading `N` accounts of an instruction, with compile-time checked
+/// indexed access. Named layouts can be defined as type aliases, e.g.
+/// `type TransferAccounts<'a> = MultiAccount<'a, 3>;`.
+#[derive(Clone, Copy)]
+pub struct MultiAccount<'a, const N: usize>(pub [&'a AccountInfo; N]);
+
+impl<'a, const N: usize> MultiAccount<'a, N> {
+    /// Takes the first `N` accounts, failing if fewer were provided.
+    pub fn new(accounts: &'a [AccountInfo]) -> Result<Self, ProgramError> {
+        expect_accounts(accounts, N)?;
+        Ok(Self(core::array::from_fn(|i| &accounts[i])))
+    }
+
+    /// Returns the account at `IDX`. Out of bounds indices fail to compile.
+    #[allow(clippy::let_unit_value)]
+    pub fn at<const IDX: usize>(&self) -> &'a AccountInfo {
+        let _ = AssertInBounds::<IDX, N>::OK;
+        self.0[IDX]
+    }
+}
+
+struct AssertInBounds<const IDX: usize, const N: usize>;
+
+impl<const IDX: usize, const N: usize> AssertInBounds<IDX, N> {
+    const OK: () = assert!(IDX < N, "account index out of bounds");
+}
+
+/// Cursor over a slice of accounts, e.g. the variable number of remaining accounts following an
+/// instruction's fixed accounts. Each `next_*` call validates and consumes one account.
+pub struct AccountSlice<'a> {
+    accounts: &'a [AccountInfo],
+    cursor: usize,
+}
+
+impl<'a> AccountSlice<'a> {
+    pub fn new(accounts: &'a [AccountInfo]) -> Self {
+        Self {
+            accounts,
+            cursor: 0,
+        }
+    }
+
+    /// Consumes the next account, failing with `NotEnoughAccountKeys` if none are left.
+    pub fn next_account(&mut self) -> Result<&'a AccountInfo, ProgramError> {
+        let account = self
+            .accounts
+            .get(self.cursor)
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        self.cursor += 1;
+        Ok(account)
+    }
+
+    /// Consumes the next account, asserting it is a signer.
+    pub fn next_signer(&mut self) -> Result<&'a AccountInfo, ProgramError> {
+        self.next_account()?.assert_signer()
+    }
+
+    /// Consumes the next account, asserting it is writable.
+    pub fn next_writable(&mut self) -> Result<&'a AccountInfo, ProgramError> {
+        self.next_account()?.assert_writable()
+    }
+
+    /// Consumes the next account, asserting it is a `T` owned by `program_id`.
+    pub fn next_of_type<T: Discriminator>(
+        &mut self,
+        program_id: &Pubkey,
+    ) -> Result<&'a AccountInfo, ProgramError> {
+        self.next_account()?.assert_type::<T>(program_id)
+    }
+
+    /// Returns the accounts not yet consumed.
+    pub fn remaining(&self) -> &'a [AccountInfo] {
+        &self.accounts[self.cursor..]
+    }
+}
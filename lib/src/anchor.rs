@@ -0,0 +1,195 @@
+use crate::AnchorDiscriminator;
+
+/// SHA-256 round constants.
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Returns the 8-byte Anchor discriminator for `preimage`, i.e. the first 8 bytes of its SHA-256
+/// digest. Anchor accounts use the preimage `account:<TypeName>`.
+pub const fn anchor_discriminator(preimage: &str) -> [u8; 8] {
+    let digest = sha256(preimage.as_bytes());
+    let mut discriminator = [0u8; 8];
+    let mut i = 0;
+    while i < 8 {
+        discriminator[i] = digest[i];
+        i += 1;
+    }
+    discriminator
+}
+
+/// Returns whether `data` starts with the Anchor discriminator of `T`.
+#[inline(always)]
+pub fn anchor_discriminator_matches<T: AnchorDiscriminator>(data: &[u8]) -> bool {
+    data.get(..8).eq(&Some(T::discriminator().as_slice()))
+}
+
+/// Computes the SHA-256 digest of `input`, usable in const contexts.
+const fn sha256(input: &[u8]) -> [u8; 32] {
+    let mut state: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    // Message length plus the 0x80 terminator and 8 length bytes, rounded up to whole blocks.
+    let total_len = (input.len() + 9).div_ceil(64) * 64;
+    let bit_len = (input.len() as u64).wrapping_mul(8);
+
+    let mut offset = 0;
+    while offset < total_len {
+        let mut w = [0u32; 64];
+        let mut i = 0;
+        while i < 64 {
+            let index = offset + i;
+            let byte = if index < input.len() {
+                input[index]
+            } else if index == input.len() {
+                0x80
+            } else if index >= total_len - 8 {
+                (bit_len >> ((total_len - 1 - index) * 8)) as u8
+            } else {
+                0
+            };
+            w[i / 4] |= (byte as u32) << ((3 - i % 4) * 8);
+            i += 1;
+        }
+
+        let mut t = 16;
+        while t < 64 {
+            let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+            let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+            w[t] = w[t - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[t - 7])
+                .wrapping_add(s1);
+            t += 1;
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        let mut t = 0;
+        while t < 64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let temp1 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[t])
+                .wrapping_add(w[t]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let temp2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(temp1);
+            d = c;
+            c = b;
+            b = a;
+            a = temp1.wrapping_add(temp2);
+            t += 1;
+        }
+
+        state[0] = state[0].wrapping_add(a);
+        state[1] = state[1].wrapping_add(b);
+        state[2] = state[2].wrapping_add(c);
+        state[3] = state[3].wrapping_add(d);
+        state[4] = state[4].wrapping_add(e);
+        state[5] = state[5].wrapping_add(f);
+        state[6] = state[6].wrapping_add(g);
+        state[7] = state[7].wrapping_add(h);
+        offset += 64;
+    }
+
+    let mut digest = [0u8; 32];
+    let mut i = 0;
+    while i < 32 {
+        digest[i] = (state[i / 4] >> ((3 - i % 4) * 8)) as u8;
+        i += 1;
+    }
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_matches_known_digests() {
+        assert_eq!(
+            sha256(b""),
+            [
+                0xe3, 0xb0, 0xc4, 0x42, 0x98, 0xfc, 0x1c, 0x14, 0x9a, 0xfb, 0xf4, 0xc8, 0x99, 0x6f,
+                0xb9, 0x24, 0x27, 0xae, 0x41, 0xe4, 0x64, 0x9b, 0x93, 0x4c, 0xa4, 0x95, 0x99, 0x1b,
+                0x78, 0x52, 0xb8, 0x55,
+            ]
+        );
+        assert_eq!(
+            sha256(b"abc"),
+            [
+                0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+                0x22, 0x23, 0xb0, 0x03, 0x61, 0xa3, 0x96, 0x17, 0x7a, 0x9c, 0xb4, 0x10, 0xff, 0x61,
+                0xf2, 0x00, 0x15, 0xad,
+            ]
+        );
+        // 56 and 64 byte inputs need a second block for the padding.
+        assert_eq!(
+            sha256(&[b'a'; 56]),
+            [
+                0xb3, 0x54, 0x39, 0xa4, 0xac, 0x6f, 0x09, 0x48, 0xb6, 0xd6, 0xf9, 0xe3, 0xc6, 0xaf,
+                0x0f, 0x5f, 0x59, 0x0c, 0xe2, 0x0f, 0x1b, 0xde, 0x70, 0x90, 0xef, 0x79, 0x70, 0x68,
+                0x6e, 0xc6, 0x73, 0x8a,
+            ]
+        );
+        assert_eq!(
+            sha256(&[b'a'; 64]),
+            [
+                0xff, 0xe0, 0x54, 0xfe, 0x7a, 0xe0, 0xcb, 0x6d, 0xc6, 0x5c, 0x3a, 0xf9, 0xb6, 0x1d,
+                0x52, 0x09, 0xf4, 0x39, 0x85, 0x1d, 0xb4, 0x3d, 0x0b, 0xa5, 0x99, 0x73, 0x37, 0xdf,
+                0x15, 0x46, 0x68, 0xeb,
+            ]
+        );
+    }
+
+    #[test]
+    fn anchor_discriminator_matches_anchor() {
+        assert_eq!(
+            anchor_discriminator("account:NewAccount"),
+            [176, 95, 4, 118, 91, 177, 125, 232]
+        );
+        assert_eq!(
+            anchor_discriminator("global:initialize"),
+            [175, 175, 109, 31, 13, 152, 155, 237]
+        );
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+    struct NewAccount {
+        data: u64,
+    }
+
+    crate::anchor_account!(NewAccount, zero_copy);
+
+    #[test]
+    fn anchor_zero_copy_account_reads_body_after_discriminator() {
+        use crate::AccountDeserialize;
+
+        let mut words = [0u64, 42];
+        let data = bytemuck::bytes_of_mut(&mut words);
+        data[..8].copy_from_slice(&[176, 95, 4, 118, 91, 177, 125, 232]);
+
+        assert_eq!(NewAccount::try_from_bytes(data).unwrap().data, 42);
+        NewAccount::try_from_bytes_mut(data).unwrap().data = 7;
+        assert_eq!(words[1], 7);
+
+        let mut words = [1u64, 42];
+        assert!(NewAccount::try_from_bytes(bytemuck::bytes_of_mut(&mut words)).is_err());
+    }
+}
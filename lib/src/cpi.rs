@@ -7,8 +7,9 @@ use pinocchio::{
     ProgramResult,
 };
 use pinocchio_system::instructions::{Allocate, Assign, CreateAccount, Transfer};
+use pinocchio_token::instructions::TransferChecked;
 
-use crate::Discriminator;
+use crate::{AccountInfoValidation, Discriminator, PdaInfo, COMPUTE_BUDGET_PROGRAM_ID};
 
 /// Creates a new program account.
 #[inline(always)]
@@ -65,6 +66,27 @@ pub fn create_account_with_bump<'a, 'info, T: Discriminator + Pod>(
     Ok(())
 }
 
+/// Creates a new program account at a previously derived PDA.
+#[inline(always)]
+pub fn create_account_with_pda<'a, T: Discriminator + Pod>(
+    target_account: &'a AccountInfo,
+    system_program: &'a AccountInfo,
+    payer: &'a AccountInfo,
+    owner: &Pubkey,
+    seeds: &[Seed],
+    pda: &PdaInfo,
+) -> ProgramResult {
+    target_account.assert_key(&pda.address)?;
+    create_account_with_bump::<T>(
+        target_account,
+        system_program,
+        payer,
+        owner,
+        seeds,
+        pda.bump,
+    )
+}
+
 /// Allocates space for a new program account.
 #[inline(always)]
 pub fn allocate_account<'a, 'info>(
@@ -94,6 +116,61 @@ pub fn allocate_account<'a, 'info>(
     )
 }
 
+/// Allocates space for a new account owned by `owner`, where the account address is a PDA of
+/// `program_id` (the invoking program). Use this instead of [`allocate_account`] when the new
+/// account is assigned to a program other than the one signing for its seeds.
+#[inline(always)]
+pub fn allocate_account_with_owner<'a, 'info>(
+    target_account: &'a AccountInfo,
+    system_program: &'a AccountInfo,
+    payer: &'a AccountInfo,
+    space: usize,
+    owner: &Pubkey,
+    program_id: &Pubkey,
+    seeds: &[Seed],
+) -> ProgramResult {
+    allocate_account_with_bump(
+        target_account,
+        system_program,
+        payer,
+        space,
+        owner,
+        seeds,
+        find_program_address(
+            seeds
+                .iter()
+                .map(|s| s.as_ref())
+                .collect::<Vec<_>>()
+                .as_slice(),
+            program_id,
+        )
+        .1,
+    )
+}
+
+/// Allocates space for a new program account at a previously derived PDA.
+#[inline(always)]
+pub fn allocate_account_with_pda<'a>(
+    target_account: &'a AccountInfo,
+    system_program: &'a AccountInfo,
+    payer: &'a AccountInfo,
+    space: usize,
+    owner: &Pubkey,
+    seeds: &[Seed],
+    pda: &PdaInfo,
+) -> ProgramResult {
+    target_account.assert_key(&pda.address)?;
+    allocate_account_with_bump(
+        target_account,
+        system_program,
+        payer,
+        space,
+        owner,
+        seeds,
+        pda.bump,
+    )
+}
+
 /// Allocates space for a new program account with user-provided bump.
 #[inline(always)]
 pub fn allocate_account_with_bump<'a, 'info>(
@@ -114,8 +191,55 @@ pub fn allocate_account_with_bump<'a, 'info>(
     let seeds = combined_seeds.as_slice();
     let signer = Signer::from(seeds);
 
-    let signers = &[signer];
+    allocate_account_with_signers(
+        target_account,
+        system_program,
+        payer,
+        space,
+        owner,
+        &[signer],
+    )
+}
+
+/// Allocates space for a new account, signing the system program CPIs with the provided signer
+/// seeds. Each entry of `signer_seeds` is the full seed list (including bump) of one PDA signer.
+#[inline(always)]
+pub fn allocate_account_signed<'a>(
+    target_account: &'a AccountInfo,
+    system_program: &'a AccountInfo,
+    payer: &'a AccountInfo,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let seeds = signer_seeds
+        .iter()
+        .map(|seeds| seeds.iter().map(|s| Seed::from(*s)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let signers = seeds
+        .iter()
+        .map(|seeds| Signer::from(seeds.as_slice()))
+        .collect::<Vec<_>>();
 
+    allocate_account_with_signers(
+        target_account,
+        system_program,
+        payer,
+        space,
+        owner,
+        signers.as_slice(),
+    )
+}
+
+#[inline(always)]
+fn allocate_account_with_signers<'a>(
+    target_account: &'a AccountInfo,
+    _system_program: &'a AccountInfo,
+    payer: &'a AccountInfo,
+    space: usize,
+    owner: &Pubkey,
+    signers: &[Signer],
+) -> ProgramResult {
     // Allocate space for account
     let rent = Rent::get()?;
     if target_account.lamports().eq(&0) {
@@ -136,14 +260,8 @@ pub fn allocate_account_with_bump<'a, 'info>(
         //     &[seeds],
         // )?;
 
-        CreateAccount {
-            from: payer,
-            to: target_account,
-            lamports: rent.minimum_balance(space),
-            space: space as u64,
-            owner,
-        }
-        .invoke_signed(signers)?;
+        create_account_instruction(payer, target_account, space, owner, &rent)
+            .invoke_signed(signers)?;
     } else {
         // Otherwise, if balance is nonzero:
 
@@ -203,6 +321,25 @@ pub fn allocate_account_with_bump<'a, 'info>(
     Ok(())
 }
 
+/// Builds the system program `CreateAccount` for a new account of `space` bytes owned by `owner`,
+/// funded to its rent-exempt minimum.
+#[inline(always)]
+fn create_account_instruction<'a>(
+    payer: &'a AccountInfo,
+    target_account: &'a AccountInfo,
+    space: usize,
+    owner: &'a Pubkey,
+    rent: &Rent,
+) -> CreateAccount<'a> {
+    CreateAccount {
+        from: payer,
+        to: target_account,
+        lamports: rent.minimum_balance(space),
+        space: space as u64,
+        owner,
+    }
+}
+
 /// Closes an account and returns the remaining rent lamports to the provided recipient.
 #[inline(always)]
 pub fn close_account<'info>(account_info: &AccountInfo, recipient: &AccountInfo) -> ProgramResult {
@@ -257,6 +394,137 @@ pub fn invoke_signed_with_bump<'info, const ACCOUNTS: usize>(
     pinocchio::program::invoke_signed::<ACCOUNTS>(instruction, account_infos, signers)
 }
 
+/// Transfers tokens with `transfer_checked`, which validates the mint and its decimals. Use this
+/// and its `_signed` variants for all token transfers; the unchecked token `Transfer` instruction
+/// does not confirm which mint the tokens belong to.
+#[inline(always)]
+pub fn transfer_checked(
+    authority_info: &AccountInfo,
+    from_info: &AccountInfo,
+    mint_info: &AccountInfo,
+    to_info: &AccountInfo,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    TransferChecked {
+        from: from_info,
+        mint: mint_info,
+        to: to_info,
+        authority: authority_info,
+        amount,
+        decimals,
+    }
+    .invoke()
+}
+
+/// Transfers tokens with `transfer_checked`, signing for a PDA authority of `program_id`.
+#[allow(clippy::too_many_arguments)]
+#[inline(always)]
+pub fn transfer_checked_signed(
+    authority_info: &AccountInfo,
+    from_info: &AccountInfo,
+    mint_info: &AccountInfo,
+    to_info: &AccountInfo,
+    amount: u64,
+    decimals: u8,
+    program_id: &Pubkey,
+    seeds: &[Seed],
+) -> ProgramResult {
+    let bump = find_program_address(
+        seeds
+            .iter()
+            .map(|s| s.as_ref())
+            .collect::<Vec<_>>()
+            .as_slice(),
+        program_id,
+    )
+    .1;
+    transfer_checked_signed_with_bump(
+        authority_info,
+        from_info,
+        mint_info,
+        to_info,
+        amount,
+        decimals,
+        seeds,
+        bump,
+    )
+}
+
+/// Transfers tokens with `transfer_checked`, signing for a PDA authority with the provided bump.
+#[allow(clippy::too_many_arguments)]
+#[inline(always)]
+pub fn transfer_checked_signed_with_bump(
+    authority_info: &AccountInfo,
+    from_info: &AccountInfo,
+    mint_info: &AccountInfo,
+    to_info: &AccountInfo,
+    amount: u64,
+    decimals: u8,
+    seeds: &[Seed],
+    bump: u8,
+) -> ProgramResult {
+    // Combine seeds
+    let bump_slice = &[bump];
+    let mut combined_seeds = Vec::with_capacity(seeds.len() + 1);
+    combined_seeds.extend_from_slice(seeds);
+    combined_seeds.push(Seed::from(bump_slice));
+
+    TransferChecked {
+        from: from_info,
+        mint: mint_info,
+        to: to_info,
+        authority: authority_info,
+        amount,
+        decimals,
+    }
+    .invoke_signed(&[Signer::from(combined_seeds.as_slice())])
+}
+
+/// Compute budget program instruction with its data held inline, so an `Instruction` can
+/// borrow from it.
+///
+/// The runtime only honors compute budget instructions included at the top level of a
+/// transaction; invoking them through CPI has no effect on the budget. Use these when building
+/// transactions, e.g. in tests and clients.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct ComputeBudgetInstruction<const N: usize> {
+    pub data: [u8; N],
+}
+
+impl<const N: usize> ComputeBudgetInstruction<N> {
+    pub fn as_instruction(&self) -> Instruction<'_, '_, '_, '_> {
+        Instruction {
+            program_id: &COMPUTE_BUDGET_PROGRAM_ID,
+            data: &self.data,
+            accounts: &[],
+        }
+    }
+}
+
+/// Builds the deprecated `RequestUnits` instruction, requesting `units` compute units with no
+/// additional fee. Prefer [`set_compute_unit_limit`] and [`set_compute_unit_price`].
+pub fn request_compute_units(units: u32) -> ComputeBudgetInstruction<9> {
+    let mut data = [0u8; 9];
+    data[1..5].copy_from_slice(&units.to_le_bytes());
+    ComputeBudgetInstruction { data }
+}
+
+/// Builds the `SetComputeUnitLimit` instruction.
+pub fn set_compute_unit_limit(units: u32) -> ComputeBudgetInstruction<5> {
+    let mut data = [2u8; 5];
+    data[1..].copy_from_slice(&units.to_le_bytes());
+    ComputeBudgetInstruction { data }
+}
+
+/// Builds the `SetComputeUnitPrice` instruction, with the price in micro-lamports per compute
+/// unit.
+pub fn set_compute_unit_price(micro_lamports: u64) -> ComputeBudgetInstruction<9> {
+    let mut data = [3u8; 9];
+    data[1..].copy_from_slice(&micro_lamports.to_le_bytes());
+    ComputeBudgetInstruction { data }
+}
+
 #[cfg(feature = "spl")]
 #[inline(always)]
 pub fn create_associated_token_account<'info>(
@@ -287,86 +555,6 @@ pub fn create_associated_token_account<'info>(
     )
 }
 
-#[cfg(feature = "spl")]
-#[inline(always)]
-pub fn transfer<'info>(
-    authority_info: &AccountInfo<'info>,
-    from_info: &AccountInfo<'info>,
-    to_info: &AccountInfo<'info>,
-    token_program: &AccountInfo<'info>,
-    amount: u64,
-) -> ProgramResult {
-    solana_program::program::invoke(
-        &spl_token::instruction::transfer(
-            &spl_token::ID,
-            from_info.key,
-            to_info.key,
-            authority_info.key,
-            &[authority_info.key],
-            amount,
-        )?,
-        &[
-            token_program.clone(),
-            from_info.clone(),
-            to_info.clone(),
-            authority_info.clone(),
-        ],
-    )
-}
-
-#[cfg(feature = "spl")]
-#[inline(always)]
-pub fn transfer_signed<'info>(
-    authority_info: &AccountInfo<'info>,
-    from_info: &AccountInfo<'info>,
-    to_info: &AccountInfo<'info>,
-    token_program: &AccountInfo<'info>,
-    amount: u64,
-    seeds: &[&[u8]],
-) -> ProgramResult {
-    let bump = Pubkey::find_program_address(seeds, authority_info.owner).1;
-    transfer_signed_with_bump(
-        authority_info,
-        from_info,
-        to_info,
-        token_program,
-        amount,
-        seeds,
-        bump,
-    )
-}
-
-#[cfg(feature = "spl")]
-#[inline(always)]
-pub fn transfer_signed_with_bump<'info>(
-    authority_info: &AccountInfo<'info>,
-    from_info: &AccountInfo<'info>,
-    to_info: &AccountInfo<'info>,
-    token_program: &AccountInfo<'info>,
-    amount: u64,
-    seeds: &[&[u8]],
-    bump: u8,
-) -> ProgramResult {
-    invoke_signed_with_bump(
-        &spl_token::instruction::transfer(
-            &spl_token::ID,
-            from_info.key,
-            to_info.key,
-            authority_info.key,
-            &[authority_info.key],
-            amount,
-        )?,
-        &[
-            token_program.clone(),
-            from_info.clone(),
-            to_info.clone(),
-            authority_info.clone(),
-        ],
-        seeds,
-        bump,
-    )
-}
-
 #[cfg(feature = "spl")]
 #[inline(always)]
 pub fn mint_to_signed<'info>(
@@ -887,3 +1075,29 @@ pub fn revoke_signed_with_bump<'info>(
         bump,
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestAccount;
+
+    #[test]
+    fn create_account_instruction_uses_given_owner() {
+        let owner = [9; 32];
+        let rent = Rent {
+            lamports_per_byte_year: 3480,
+            exemption_threshold: 2.0,
+            burn_percent: 50,
+        };
+        let mut payer = TestAccount::new([1; 32], [0; 32], 1_000_000_000, &[]).signer();
+        let mut target = TestAccount::new([2; 32], [0; 32], 0, &[]);
+        let (payer, target) = (payer.info(), target.info());
+
+        let instruction = create_account_instruction(&payer, &target, 100, &owner, &rent);
+
+        assert_eq!(instruction.owner, &owner);
+        assert_eq!(instruction.space, 100);
+        assert_eq!(instruction.lamports, rent.minimum_balance(100));
+        assert_eq!(instruction.to.key(), target.key());
+    }
+}
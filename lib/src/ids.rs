@@ -0,0 +1,21 @@
+use pinocchio::pubkey::Pubkey;
+
+/// Address of the associated token account program.
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// Address of the upgradeable BPF loader, which owns upgradeable programs and their data accounts.
+pub const BPF_LOADER_UPGRADEABLE_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("BPFLoaderUpgradeab1e11111111111111111111111");
+
+/// Address of the compute budget program.
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("ComputeBudget111111111111111111111111111111");
+
+/// Address of the native mint, whose tokens wrap SOL.
+pub const NATIVE_MINT_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("So11111111111111111111111111111111111111112");
+
+/// Address of the Token-2022 (Token Extensions) program.
+pub const TOKEN_2022_PROGRAM_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
@@ -0,0 +1,39 @@
+use std::marker::PhantomData;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use pinocchio::{account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{AccountInfoValidation, AsAccount, Discriminator};
+
+/// Account wrapper that defers deserialization until the data is accessed, so branches that
+/// never read the account do not pay for it.
+pub struct LazyAccount<'a, T> {
+    info: &'a AccountInfo,
+    program_id: &'a Pubkey,
+    _phantom: PhantomData<T>,
+}
+
+impl<'a, T> LazyAccount<'a, T>
+where
+    T: BorshDeserialize + BorshSerialize + Discriminator,
+{
+    /// Wraps the account, checking only its discriminator byte.
+    pub fn new(info: &'a AccountInfo, program_id: &'a Pubkey) -> Result<Self, ProgramError> {
+        info.assert_discriminator::<T>()?;
+        Ok(Self {
+            info,
+            program_id,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns the underlying account info.
+    pub fn info(&self) -> &'a AccountInfo {
+        self.info
+    }
+
+    /// Asserts the account owner and deserializes the account data.
+    pub fn get(&self) -> Result<T, ProgramError> {
+        self.info.as_account(self.program_id)
+    }
+}
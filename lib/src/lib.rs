@@ -1,16 +1,37 @@
+mod accounts;
+mod anchor;
 mod cpi;
+mod ids;
+mod lazy;
 mod loaders;
 pub mod macros;
+mod pda;
+#[cfg(feature = "program-test")]
+pub mod program_test;
+mod sysvars;
+#[cfg(test)]
+mod test_utils;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod traits;
 mod utils;
 
+pub use accounts::*;
+pub use anchor::*;
 use borsh::{BorshDeserialize, BorshSerialize};
 pub use cpi::*;
+pub use ids::*;
+pub use lazy::*;
+pub use pda::*;
+pub use sysvars::*;
 pub use traits::*;
 pub use utils::*;
 
 pub use bytemuck::{Pod, Zeroable};
+pub use geppetto_derive::{schema, AccountBuilder, BorshSize};
 pub use num_enum::{IntoPrimitive, TryFromPrimitive};
+#[doc(hidden)]
+pub use paste;
 pub use thiserror::Error;
 
 // #[repr(u8)]
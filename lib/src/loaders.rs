@@ -1,22 +1,35 @@
 use borsh::{BorshDeserialize, BorshSerialize};
+use bytemuck::Pod;
 use pinocchio::{
-    account_info::AccountInfo,
-    instruction::Seed,
+    account_info::{AccountInfo, Ref, RefMut},
+    instruction::{Seed, Signer},
     msg,
     program_error::ProgramError,
-    pubkey::{self, find_program_address, Pubkey},
+    pubkey::{self, create_program_address, find_program_address, Pubkey},
+    sysvars::{clock::Clock, rent::Rent, Sysvar},
 };
 use pinocchio_system::instructions::Transfer;
-#[cfg(feature = "spl")]
-use solana_program::program_pack::Pack;
+use pinocchio_token::{
+    instructions::{Approve, Burn, MintToChecked, SyncNative, TransferChecked},
+    state::{Mint, TokenAccount},
+};
+#[cfg(feature = "spl2022")]
+use spl_token_2022::extension::StateWithExtensions;
 
 use crate::{
-    allocate_account, AccountInfoValidation, AsAccount, CloseAccount, Discriminator,
-    LamportTransfer,
+    allocate_account, anchor_discriminator_matches, classify_mint, is_on_curve, try_pod_body_mut,
+    validate_stored_bump_pda, AccountDiscriminator, AccountInfoValidation, AccountVersion,
+    AnchorDiscriminator, AsAccount, AsSplToken, BorshSize, CloseAccount, Discriminator,
+    DiscriminatorWidth, EpochSchedule, LamportTransfer, MintCapValidation, MintState,
+    MintValidation, SeedBuilder, SplTokenCpi, SysvarAccess, ASSOCIATED_TOKEN_PROGRAM_ID,
+    BPF_LOADER_UPGRADEABLE_ID, CLOCK_ID, EPOCH_SCHEDULE_ID, NATIVE_MINT_ID, POD_BODY_OFFSET,
+    RENT_ID, SYSVAR_PROGRAM_ID,
 };
 
 #[cfg(feature = "spl")]
-use crate::{AccountValidation, AsSplToken};
+use crate::AccountValidation;
+#[cfg(feature = "spl2022")]
+use crate::{AsSplToken2022, TOKEN_2022_PROGRAM_ID};
 
 impl AccountInfoValidation for AccountInfo {
     fn assert_signer(&self) -> Result<&Self, ProgramError> {
@@ -28,6 +41,15 @@ impl AccountInfoValidation for AccountInfo {
         Ok(self)
     }
 
+    fn assert_not_signer(&self) -> Result<&Self, ProgramError> {
+        if self.is_signer() {
+            msg!("Account is a signer:");
+            pubkey::log(self.key());
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(self)
+    }
+
     fn assert_writable(&self) -> Result<&Self, ProgramError> {
         if !self.is_writable() {
             msg!("Account is not writable:");
@@ -37,6 +59,30 @@ impl AccountInfoValidation for AccountInfo {
         Ok(self)
     }
 
+    fn assert_writable_if(&self, condition: bool) -> Result<&Self, ProgramError> {
+        if condition {
+            return self.assert_writable();
+        }
+        Ok(self)
+    }
+
+    fn assert_not_writable(&self) -> Result<&Self, ProgramError> {
+        if self.is_writable() {
+            msg!("Account is writable:");
+            pubkey::log(self.key());
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(self)
+    }
+
+    fn assert_writable_matches(&self, should_be_writable: bool) -> Result<&Self, ProgramError> {
+        if should_be_writable {
+            self.assert_writable()
+        } else {
+            self.assert_not_writable()
+        }
+    }
+
     fn assert_executable(&self) -> Result<&Self, ProgramError> {
         if !self.executable() {
             msg!("Account is not executable:");
@@ -59,7 +105,64 @@ impl AccountInfoValidation for AccountInfo {
         if self.data_is_empty() {
             msg!("Account is empty:");
             pubkey::log(self.key());
-            return Err(ProgramError::AccountAlreadyInitialized);
+            return Err(ProgramError::UninitializedAccount);
+        }
+        Ok(self)
+    }
+
+    fn assert_lamports_gte(&self, min: u64) -> Result<&Self, ProgramError> {
+        if self.lamports().lt(&min) {
+            msg!(
+                "Account has too few lamports (min, actual): {:?}, {:?}",
+                min,
+                self.lamports()
+            );
+            pubkey::log(self.key());
+            return Err(ProgramError::InsufficientFunds);
+        }
+        Ok(self)
+    }
+
+    fn assert_lamports_lt(&self, max: u64) -> Result<&Self, ProgramError> {
+        if self.lamports().ge(&max) {
+            msg!(
+                "Account has too many lamports (max exclusive, actual): {:?}, {:?}",
+                max,
+                self.lamports()
+            );
+            pubkey::log(self.key());
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(self)
+    }
+
+    fn assert_rent_exempt(&self, rent: &Rent) -> Result<&Self, ProgramError> {
+        let minimum_balance = rent.minimum_balance(self.data_len());
+        if self.lamports().lt(&minimum_balance) {
+            msg!(
+                "Account is not rent exempt (shortfall): {:?}",
+                minimum_balance - self.lamports()
+            );
+            pubkey::log(self.key());
+            return Err(ProgramError::InsufficientFunds);
+        }
+        Ok(self)
+    }
+
+    fn assert_rent_exempt_for(
+        &self,
+        data_len: usize,
+        rent_sysvar: &AccountInfo,
+    ) -> Result<&Self, ProgramError> {
+        let minimum_balance = rent_sysvar.as_rent()?.minimum_balance(data_len);
+        if self.lamports().lt(&minimum_balance) {
+            msg!(
+                "Account is not rent exempt (expected, actual): {:?}, {:?}",
+                minimum_balance,
+                self.lamports()
+            );
+            pubkey::log(self.key());
+            return Err(ProgramError::AccountNotRentExempt);
         }
         Ok(self)
     }
@@ -68,13 +171,43 @@ impl AccountInfoValidation for AccountInfo {
         self.assert_key(program_id)?.assert_executable()
     }
 
-    fn assert_type<T: Discriminator>(&self, program_id: &Pubkey) -> Result<&Self, ProgramError> {
-        self.assert_owner(program_id)?;
+    fn assert_system_program(&self) -> Result<&Self, ProgramError> {
+        self.assert_program(&pinocchio_system::ID)
+    }
 
-        let expected_discriminator = T::discriminator();
-        let actual_discriminator = self.try_borrow_data()?[0];
+    fn assert_token_program(&self) -> Result<&Self, ProgramError> {
+        self.assert_program(&pinocchio_token::ID)
+    }
 
-        if actual_discriminator.ne(&expected_discriminator) {
+    fn assert_associated_token_program(&self) -> Result<&Self, ProgramError> {
+        self.assert_program(&ASSOCIATED_TOKEN_PROGRAM_ID)
+    }
+
+    fn assert_token_program_2022(&self) -> Result<&Self, ProgramError> {
+        self.assert_program(&crate::TOKEN_2022_PROGRAM_ID)
+    }
+
+    fn assert_token_program_any(&self) -> Result<&Self, ProgramError> {
+        if self.key().ne(&pinocchio_token::ID) && self.key().ne(&crate::TOKEN_2022_PROGRAM_ID) {
+            msg!("Account is not a token program:");
+            pubkey::log(self.key());
+            return Err(ProgramError::InvalidAccountData);
+        }
+        self.assert_executable()
+    }
+
+    fn assert_type<T: AccountDiscriminator>(
+        &self,
+        program_id: &Pubkey,
+    ) -> Result<&Self, ProgramError> {
+        self.assert_owner(program_id)?.assert_discriminator::<T>()
+    }
+
+    fn assert_discriminator<T: AccountDiscriminator>(&self) -> Result<&Self, ProgramError> {
+        let expected_discriminator = T::account_discriminator();
+        let actual_discriminator = T::Width::read(&self.try_borrow_data()?);
+
+        if actual_discriminator.ne(&Some(expected_discriminator)) {
             msg!(
                 "Account is invalid type (expected, actual): {:?}, {:?}",
                 expected_discriminator,
@@ -86,6 +219,63 @@ impl AccountInfoValidation for AccountInfo {
         Ok(self)
     }
 
+    fn assert_version(&self, expected: u8) -> Result<&Self, ProgramError> {
+        let actual = self.try_borrow_data()?.get(1).copied();
+
+        if actual.ne(&Some(expected)) {
+            msg!(
+                "Account is invalid version (expected, actual): {:?}, {:?}",
+                expected,
+                actual
+            );
+            pubkey::log(self.key());
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(self)
+    }
+
+    fn assert_min_size<T: Discriminator>(&self) -> Result<&Self, ProgramError> {
+        let expected_size = POD_BODY_OFFSET + std::mem::size_of::<T>();
+
+        if self.data_len().lt(&expected_size) {
+            msg!(
+                "Account is too small for {} (expected, actual): {:?}, {:?}",
+                std::any::type_name::<T>(),
+                expected_size,
+                self.data_len()
+            );
+            pubkey::log(self.key());
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        Ok(self)
+    }
+
+    fn assert_data_len(&self, expected: usize) -> Result<&Self, ProgramError> {
+        if self.data_len().ne(&expected) {
+            msg!(
+                "Account data length mismatch (expected, actual): {:?}, {:?}",
+                expected,
+                self.data_len()
+            );
+            pubkey::log(self.key());
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(self)
+    }
+
+    fn assert_data_len_gte(&self, min: usize) -> Result<&Self, ProgramError> {
+        if self.data_len().lt(&min) {
+            msg!(
+                "Account data is too small (min, actual): {:?}, {:?}",
+                min,
+                self.data_len()
+            );
+            pubkey::log(self.key());
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        Ok(self)
+    }
+
     fn assert_owner(&self, owner: &Pubkey) -> Result<&Self, ProgramError> {
         if self.owner().ne(owner) {
             msg!("Account owner mismatch (expected, actual):");
@@ -96,6 +286,27 @@ impl AccountInfoValidation for AccountInfo {
         Ok(self)
     }
 
+    fn assert_owned_by_any(&self, owners: &[&Pubkey]) -> Result<&Self, ProgramError> {
+        if !owners.iter().any(|owner| self.owner().eq(*owner)) {
+            msg!("Account owner mismatch (actual, expected any of):");
+            pubkey::log(self.owner());
+            for owner in owners {
+                pubkey::log(owner);
+            }
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(self)
+    }
+
+    fn assert_not_owner(&self, program_id: &Pubkey) -> Result<&Self, ProgramError> {
+        if self.owner().eq(program_id) {
+            msg!("Account has forbidden owner:");
+            pubkey::log(self.key());
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+        Ok(self)
+    }
+
     fn assert_key(&self, address: &Pubkey) -> Result<&Self, ProgramError> {
         if self.key().ne(address) {
             msg!("Account key mismatch:");
@@ -106,7 +317,43 @@ impl AccountInfoValidation for AccountInfo {
         Ok(self)
     }
 
+    fn assert_key_not(&self, forbidden: &Pubkey) -> Result<&Self, ProgramError> {
+        if self.key().eq(forbidden) {
+            msg!("Account key is forbidden:");
+            pubkey::log(self.key());
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(self)
+    }
+
+    fn assert_on_curve(&self) -> Result<&Self, ProgramError> {
+        if !is_on_curve(self.key()) {
+            msg!("Account is not on curve:");
+            pubkey::log(self.key());
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(self)
+    }
+
+    fn assert_not_on_curve(&self) -> Result<&Self, ProgramError> {
+        if is_on_curve(self.key()) {
+            msg!("Account is on curve:");
+            pubkey::log(self.key());
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(self)
+    }
+
     fn assert_seeds(&self, seeds: &[&[u8]], program_id: &Pubkey) -> Result<&Self, ProgramError> {
+        self.assert_seeds_canonical(seeds, program_id)?;
+        Ok(self)
+    }
+
+    fn assert_seeds_canonical(
+        &self,
+        seeds: &[&[u8]],
+        program_id: &Pubkey,
+    ) -> Result<u8, ProgramError> {
         let pda = find_program_address(seeds, program_id);
         if self.key().ne(&pda.0) {
             msg!("Account is invalid seeds (expected, actual):");
@@ -114,45 +361,256 @@ impl AccountInfoValidation for AccountInfo {
             pubkey::log(self.key());
             return Err(ProgramError::InvalidSeeds);
         }
+        Ok(pda.1)
+    }
+
+    fn assert_seeds_with_provided_bump(
+        &self,
+        seeds: &[&[u8]],
+        bump: u8,
+        program_id: &Pubkey,
+    ) -> Result<&Self, ProgramError> {
+        let bump_slice = &[bump];
+        let mut combined_seeds = Vec::with_capacity(seeds.len() + 1);
+        combined_seeds.extend_from_slice(seeds);
+        combined_seeds.push(bump_slice.as_slice());
+
+        let pda = create_program_address(&combined_seeds, program_id)?;
+        if self.key().ne(&pda) {
+            msg!("Account is invalid seeds (expected, actual):");
+            pubkey::log(&pda);
+            pubkey::log(self.key());
+            return Err(ProgramError::InvalidSeeds);
+        }
         Ok(self)
     }
 
-    // fn is_sysvar(&self, sysvar_id: &Pubkey) -> Result<&Self, ProgramError> {
-    // self.has_owner(&pinocchio::sysvars::ID)?
-    //     .has_address(sysvar_id)
-    // }
+    fn assert_seeds_builder(
+        &self,
+        seeds: &SeedBuilder,
+        program_id: &Pubkey,
+    ) -> Result<&Self, ProgramError> {
+        match seeds.bump_seed() {
+            Some(bump) => {
+                self.assert_seeds_with_provided_bump(&seeds.seed_slices(), bump, program_id)
+            }
+            None => self.assert_seeds(&seeds.seed_slices(), program_id),
+        }
+    }
+
+    fn assert_seeds_pda_match_stored_bump<T, F>(
+        &self,
+        prefix_seeds: &[&[u8]],
+        program_id: &Pubkey,
+        get_bump: F,
+    ) -> Result<&Self, ProgramError>
+    where
+        T: Discriminator + BorshDeserialize,
+        F: Fn(&T) -> u8,
+    {
+        validate_stored_bump_pda::<T, F>(self, prefix_seeds, program_id, get_bump)?;
+        Ok(self)
+    }
+
+    fn assert_signer_err<E: Into<ProgramError>>(&self, err: E) -> Result<&Self, ProgramError> {
+        self.assert_signer().map_err(|_| err.into())
+    }
+
+    fn assert_writable_err<E: Into<ProgramError>>(&self, err: E) -> Result<&Self, ProgramError> {
+        self.assert_writable().map_err(|_| err.into())
+    }
+
+    fn assert_executable_err<E: Into<ProgramError>>(&self, err: E) -> Result<&Self, ProgramError> {
+        self.assert_executable().map_err(|_| err.into())
+    }
+
+    fn assert_empty_err<E: Into<ProgramError>>(&self, err: E) -> Result<&Self, ProgramError> {
+        self.assert_empty().map_err(|_| err.into())
+    }
+
+    fn assert_not_empty_err<E: Into<ProgramError>>(&self, err: E) -> Result<&Self, ProgramError> {
+        self.assert_not_empty().map_err(|_| err.into())
+    }
+
+    fn assert_type_err<T: AccountDiscriminator, E: Into<ProgramError>>(
+        &self,
+        program_id: &Pubkey,
+        err: E,
+    ) -> Result<&Self, ProgramError> {
+        self.assert_type::<T>(program_id).map_err(|_| err.into())
+    }
+
+    fn assert_program_err<E: Into<ProgramError>>(
+        &self,
+        program_id: &Pubkey,
+        err: E,
+    ) -> Result<&Self, ProgramError> {
+        self.assert_program(program_id).map_err(|_| err.into())
+    }
+
+    fn assert_key_err<E: Into<ProgramError>>(
+        &self,
+        address: &Pubkey,
+        err: E,
+    ) -> Result<&Self, ProgramError> {
+        self.assert_key(address).map_err(|_| err.into())
+    }
+
+    fn assert_owner_err<E: Into<ProgramError>>(
+        &self,
+        program_id: &Pubkey,
+        err: E,
+    ) -> Result<&Self, ProgramError> {
+        self.assert_owner(program_id).map_err(|_| err.into())
+    }
+
+    fn assert_seeds_err<E: Into<ProgramError>>(
+        &self,
+        seeds: &[&[u8]],
+        program_id: &Pubkey,
+        err: E,
+    ) -> Result<&Self, ProgramError> {
+        self.assert_seeds(seeds, program_id).map_err(|_| err.into())
+    }
+
+    fn assert_sysvar(&self, sysvar_id: &Pubkey) -> Result<&Self, ProgramError> {
+        self.assert_owner_err(&SYSVAR_PROGRAM_ID, ProgramError::InvalidArgument)?
+            .assert_key_err(sysvar_id, ProgramError::InvalidArgument)
+    }
+}
+
+impl SysvarAccess for AccountInfo {
+    fn as_clock(&self) -> Result<Clock, ProgramError> {
+        let data = self.assert_sysvar(&CLOCK_ID)?.try_borrow_data()?;
+        if data.len().lt(&40) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Clock {
+            slot: read_u64(&data, 0),
+            epoch_start_timestamp: read_u64(&data, 8) as i64,
+            epoch: read_u64(&data, 16),
+            leader_schedule_epoch: read_u64(&data, 24),
+            unix_timestamp: read_u64(&data, 32) as i64,
+        })
+    }
+
+    fn as_rent(&self) -> Result<Rent, ProgramError> {
+        let data = self.assert_sysvar(&RENT_ID)?.try_borrow_data()?;
+        if data.len().lt(&17) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(Rent {
+            lamports_per_byte_year: read_u64(&data, 0),
+            exemption_threshold: f64::from_bits(read_u64(&data, 8)),
+            burn_percent: data[16],
+        })
+    }
+
+    fn as_epoch_schedule(&self) -> Result<EpochSchedule, ProgramError> {
+        let data = self.assert_sysvar(&EPOCH_SCHEDULE_ID)?.try_borrow_data()?;
+        if data.len().lt(&EpochSchedule::LEN) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(EpochSchedule {
+            slots_per_epoch: read_u64(&data, 0),
+            leader_schedule_slot_offset: read_u64(&data, 8),
+            warmup: data[16].ne(&0),
+            first_normal_epoch: read_u64(&data, 17),
+            first_normal_slot: read_u64(&data, 25),
+        })
+    }
+}
+
+/// Reads a little-endian `u64` at `offset`. The caller must have checked the data length.
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
 }
 
 impl AsAccount for AccountInfo {
     fn as_account<T>(&self, program_id: &Pubkey) -> Result<T, ProgramError>
     where
-        T: BorshDeserialize + BorshSerialize + Discriminator,
+        T: BorshDeserialize + BorshSerialize + AccountDiscriminator,
     {
         self.assert_owner(program_id)?;
-        T::try_from_slice(&self.try_borrow_data()?[1..])
+        let data = self.try_borrow_data()?;
+        // Accounts may be allocated with trailing space, so do not require the whole buffer to be
+        // consumed.
+        T::deserialize(&mut data.get(T::Width::LEN..).unwrap_or_default())
             .map_err(|_| ProgramError::InvalidAccountData)
     }
 
+    fn as_account_option<T>(&self, program_id: &Pubkey) -> Result<Option<T>, ProgramError>
+    where
+        T: BorshDeserialize + BorshSerialize + AccountDiscriminator,
+    {
+        if self.data_is_empty() {
+            return Ok(None);
+        }
+        self.as_account(program_id).map(Some)
+    }
+
+    fn as_anchor_account<T>(&self, program_id: &Pubkey) -> Result<T, ProgramError>
+    where
+        T: BorshDeserialize + AnchorDiscriminator,
+    {
+        self.assert_owner(program_id)?;
+        let data = self.try_borrow_data()?;
+        if !anchor_discriminator_matches::<T>(&data) {
+            msg!("Account has invalid anchor discriminator:");
+            pubkey::log(self.key());
+            return Err(ProgramError::InvalidAccountData);
+        }
+        // Anchor accounts may be allocated with trailing space, so do not require the whole
+        // buffer to be consumed.
+        T::deserialize(&mut &data[8..]).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
     fn save_account<T>(&self, program_id: &Pubkey, data: &T) -> Result<(), ProgramError>
     where
-        T: BorshDeserialize + BorshSerialize + Discriminator,
+        T: BorshDeserialize + BorshSerialize + AccountDiscriminator,
     {
         self.assert_owner(program_id)?.assert_writable()?;
 
-        let mut account_data_ref = self.try_borrow_mut_data()?;
-        account_data_ref[0] = T::discriminator();
+        let serialized_data = data
+            .try_to_vec()
+            .map_err(|_| ProgramError::InvalidAccountData)?;
 
-        // TODO: Need to resize account data if it's not enough.
+        // Grow the account if the serialized data no longer fits.
+        resize_account(self, T::Width::LEN + serialized_data.len())?;
 
-        account_data_ref[1..].copy_from_slice(
-            &data
-                .try_to_vec()
-                .map_err(|_| ProgramError::InvalidAccountData)?,
-        );
+        let mut account_data_ref = self.try_borrow_mut_data()?;
+        T::account_discriminator().write(&mut account_data_ref);
+        write_account_body(&mut account_data_ref, T::Width::LEN, &serialized_data);
         Ok(())
     }
 
-    // TODO: Program_id is the same as owner DUH
+    fn save_account_if_changed<T>(
+        &self,
+        program_id: &Pubkey,
+        data: &T,
+    ) -> Result<bool, ProgramError>
+    where
+        T: BorshDeserialize + BorshSerialize + AccountDiscriminator,
+    {
+        self.assert_owner(program_id)?.assert_writable()?;
+
+        let serialized_data = data
+            .try_to_vec()
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        {
+            let account_data_ref = self.try_borrow_data()?;
+            if T::discriminator_matches(&account_data_ref)
+                && account_body_matches(&account_data_ref, T::Width::LEN, &serialized_data)
+            {
+                return Ok(false);
+            }
+        }
+
+        self.save_account(program_id, data)?;
+        Ok(true)
+    }
+
     fn create_account<T>(
         &self,
         data: &T,
@@ -162,7 +620,7 @@ impl AsAccount for AccountInfo {
         seeds: &[Seed],
     ) -> Result<(), ProgramError>
     where
-        T: BorshDeserialize + BorshSerialize + Discriminator,
+        T: BorshDeserialize + BorshSerialize + BorshSize + AccountDiscriminator,
     {
         self.assert_empty()?
             .assert_owner(system_program.key())?
@@ -172,41 +630,162 @@ impl AsAccount for AccountInfo {
             .try_to_vec()
             .map_err(|_| ProgramError::InvalidAccountData)?;
 
-        let space = 1 + serialized_data.len();
+        let space = crate::account_size!(T);
+        if space.lt(&(T::Width::LEN + serialized_data.len())) {
+            msg!(
+                "Serialized {} exceeds its MAX_SIZE (max, actual): {:?}, {:?}",
+                std::any::type_name::<T>(),
+                T::MAX_SIZE,
+                serialized_data.len()
+            );
+            return Err(ProgramError::InvalidAccountData);
+        }
 
         allocate_account(self, system_program, payer, space, owner, seeds)?;
 
         let mut data = self.try_borrow_mut_data()?;
-        data[0] = T::discriminator();
+        T::account_discriminator().write(&mut data);
+        write_account_body(&mut data, T::Width::LEN, &serialized_data);
 
-        data[1..].copy_from_slice(&serialized_data);
+        Ok(())
+    }
 
+    fn as_versioned_account<T>(&self, program_id: &Pubkey) -> Result<T, ProgramError>
+    where
+        T: BorshDeserialize + Discriminator + AccountVersion,
+    {
+        self.assert_type::<T>(program_id)?
+            .assert_version(T::version())?;
+        let data = self.try_borrow_data()?;
+        T::deserialize(&mut &data[2..]).map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    fn save_versioned_account<T>(&self, program_id: &Pubkey, data: &T) -> Result<(), ProgramError>
+    where
+        T: BorshSerialize + Discriminator + AccountVersion,
+    {
+        self.assert_owner(program_id)?.assert_writable()?;
+
+        let serialized_data = data
+            .try_to_vec()
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        resize_account(self, 2 + serialized_data.len())?;
+
+        let mut account_data_ref = self.try_borrow_mut_data()?;
+        account_data_ref[0] = T::discriminator();
+        account_data_ref[1] = T::version();
+        write_account_body(&mut account_data_ref, 2, &serialized_data);
         Ok(())
     }
 
-    // fn as_account_mut<T>(&self, program_id: &Pubkey) -> Result<&mut T, ProgramError>
-    // where
-    //     T: BorshDeserialize + BorshSerialize + Discriminator,
-    // {
-    //     unsafe {
-    //         self.assert_owner(program_id)?;
-    //         T::try_from_bytes_mut(std::slice::from_raw_parts_mut(
-    //             self.try_borrow_mut_data()?.as_mut_ptr(),
-    //             8 + std::mem::size_of::<T>(),
-    //         ))
-    //     }
-    // }
+    fn migrate_account<Old, New, F>(
+        &self,
+        program_id: &Pubkey,
+        migrate: F,
+    ) -> Result<New, ProgramError>
+    where
+        Old: BorshDeserialize + Discriminator + AccountVersion,
+        New: BorshSerialize + Discriminator + AccountVersion,
+        F: FnOnce(Old) -> New,
+    {
+        let migrated = migrate(self.as_versioned_account::<Old>(program_id)?);
+        self.save_versioned_account(program_id, &migrated)?;
+        Ok(migrated)
+    }
+
+    fn as_account_mut<T>(&self, program_id: &Pubkey) -> Result<RefMut<'_, T>, ProgramError>
+    where
+        T: Pod + Discriminator,
+    {
+        self.assert_type::<T>(program_id)?.assert_writable()?;
+
+        let mut data = self.try_borrow_mut_data()?;
+        try_pod_body_mut::<T>(&mut data)?;
+        // The length and alignment were checked above, so the cast cannot fail.
+        Ok(RefMut::map(data, |data| {
+            bytemuck::from_bytes_mut::<T>(
+                &mut data[POD_BODY_OFFSET..POD_BODY_OFFSET + std::mem::size_of::<T>()],
+            )
+        }))
+    }
+}
+
+/// Grows the account to `new_size` bytes if it is smaller, first checking it holds enough
+/// lamports to stay rent exempt. Never shrinks the account, so space allocated up front (e.g. from
+/// `BorshSize::MAX_SIZE`) is kept, along with the rent paid for it.
+fn resize_account(info: &AccountInfo, new_size: usize) -> Result<(), ProgramError> {
+    if info.data_len().ge(&new_size) {
+        return Ok(());
+    }
+    grow_account(info, new_size, &Rent::get()?)
+}
+
+/// Grows the account to `new_size` bytes, failing with `AccountNotRentExempt` if it does not
+/// hold the rent exempt minimum for the new size.
+fn grow_account(info: &AccountInfo, new_size: usize, rent: &Rent) -> Result<(), ProgramError> {
+    if info.lamports().lt(&rent.minimum_balance(new_size)) {
+        msg!(
+            "Account lacks lamports for rent after resize (size, lamports): {:?}, {:?}",
+            new_size,
+            info.lamports()
+        );
+        pubkey::log(info.key());
+        return Err(ProgramError::AccountNotRentExempt);
+    }
+    info.realloc(new_size, false).map_err(|err| {
+        msg!("Failed to resize account to {:?} bytes:", new_size);
+        pubkey::log(info.key());
+        err
+    })
+}
+
+/// Writes `body` at `offset`, zeroing any trailing space left over from a larger previous value.
+/// The caller must have checked the data can hold the body.
+fn write_account_body(data: &mut [u8], offset: usize, body: &[u8]) {
+    let (body_data, tail) = data[offset..].split_at_mut(body.len());
+    body_data.copy_from_slice(body);
+    tail.fill(0);
+}
+
+/// Returns whether the data holds `body` at `offset` followed only by zeroed trailing space, as
+/// written by [`write_account_body`].
+fn account_body_matches(data: &[u8], offset: usize, body: &[u8]) -> bool {
+    data.get(offset..offset + body.len()).eq(&Some(body))
+        && data[offset + body.len()..].iter().all(|byte| byte.eq(&0))
 }
 
 impl<'a> LamportTransfer<'a> for AccountInfo {
-    // TODO: This way of transfer is non-standard and doesn't show up in explorers.
     #[inline(always)]
-    fn send(&'a self, lamports: u64, to: &'a AccountInfo) -> Result<(), ProgramError> {
+    fn send_safe(&'a self, lamports: u64, to: &'a AccountInfo) -> Result<(), ProgramError> {
+        let remaining = self
+            .lamports()
+            .checked_sub(lamports)
+            .ok_or(ProgramError::InsufficientFunds)?;
+        if self.key().eq(to.key()) {
+            return Ok(());
+        }
+        let received = to
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        *self.try_borrow_mut_lamports()? = remaining;
+        *to.try_borrow_mut_lamports()? = received;
+        Ok(())
+    }
+
+    #[inline(always)]
+    fn send_unchecked(&'a self, lamports: u64, to: &'a AccountInfo) -> Result<(), ProgramError> {
         *self.try_borrow_mut_lamports()? -= lamports;
         *to.try_borrow_mut_lamports()? += lamports;
         Ok(())
     }
 
+    #[inline(always)]
+    fn send_all(&'a self, to: &'a AccountInfo) -> Result<(), ProgramError> {
+        self.send_safe(self.lamports(), to)
+    }
+
     #[inline(always)]
     fn collect(&'a self, lamports: u64, from: &'a AccountInfo) -> Result<(), ProgramError> {
         Transfer {
@@ -224,43 +803,297 @@ impl<'a> CloseAccount<'a> for AccountInfo {
         self.realloc(0, true)?;
 
         // Return rent lamports.
-        self.send(self.lamports(), to);
+        self.send_safe(self.lamports(), to)?;
 
         Ok(())
     }
-}
 
-#[cfg(feature = "spl")]
-impl AsSplToken for AccountInfo<'_> {
-    fn as_mint(&self) -> Result<spl_token::state::Mint, ProgramError> {
-        unsafe {
-            self.has_owner(&spl_token::ID)?;
-            spl_token::state::Mint::unpack(std::slice::from_raw_parts(
-                self.try_borrow_data()?.as_ptr(),
-                spl_token::state::Mint::LEN,
-            ))
+    fn close_with_discriminator_zero(&'a self, to: &'a AccountInfo) -> Result<(), ProgramError> {
+        // Wipe discriminator.
+        if let Some(discriminator) = self.try_borrow_mut_data()?.first_mut() {
+            *discriminator = 0;
         }
+
+        self.close(to)
     }
 
-    fn as_token_account(&self) -> Result<spl_token::state::Account, ProgramError> {
-        unsafe {
-            self.has_owner(&spl_token::ID)?;
-            spl_token::state::Account::unpack(std::slice::from_raw_parts(
-                self.try_borrow_data()?.as_ptr(),
-                spl_token::state::Account::LEN,
-            ))
+    fn close_to_token_account(
+        &'a self,
+        vault: &'a AccountInfo,
+        token_program: &'a AccountInfo,
+    ) -> Result<(), ProgramError> {
+        token_program.assert_program(&pinocchio_token::ID)?;
+        vault
+            .assert_owner(&pinocchio_token::ID)?
+            .assert_writable()?;
+
+        self.close(vault)?;
+
+        // Update the wrapped SOL balance to match the vault's lamports.
+        SyncNative {
+            native_token: vault,
         }
+        .invoke()
+    }
+
+    fn close_into_program_authority(
+        &'a self,
+        program_data: &'a AccountInfo,
+        authority: &'a AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        program_data
+            .assert_owner(&BPF_LOADER_UPGRADEABLE_ID)?
+            .assert_seeds(&[program_id.as_slice()], &BPF_LOADER_UPGRADEABLE_ID)?;
+
+        // Layout: u32 state tag (3 for `ProgramData`), u64 slot, then an optional authority.
+        {
+            let data = program_data.try_borrow_data()?;
+            if data.get(..4).ne(&Some(3u32.to_le_bytes().as_slice())) {
+                return Err(ProgramError::InvalidAccountData);
+            }
+            match data.get(12) {
+                Some(1) => {
+                    let upgrade_authority =
+                        data.get(13..45).ok_or(ProgramError::InvalidAccountData)?;
+                    if authority.key().as_slice().ne(upgrade_authority) {
+                        msg!("Account is not the program upgrade authority:");
+                        pubkey::log(authority.key());
+                        return Err(ProgramError::IncorrectAuthority);
+                    }
+                }
+                Some(0) => {
+                    msg!("Program has no upgrade authority");
+                    return Err(ProgramError::IncorrectAuthority);
+                }
+                _ => return Err(ProgramError::InvalidAccountData),
+            }
+        }
+
+        self.close(authority)
+    }
+}
+
+impl AsSplToken for AccountInfo {
+    fn as_mint(&self) -> Result<Ref<'_, Mint>, ProgramError> {
+        Mint::from_account_info(self)
+    }
+
+    fn as_token_account(&self) -> Result<Ref<'_, TokenAccount>, ProgramError> {
+        TokenAccount::from_account_info(self)
     }
 
     fn as_associated_token_account(
         &self,
         owner: &Pubkey,
         mint: &Pubkey,
-    ) -> Result<spl_token::state::Account, ProgramError> {
-        self.has_address(&spl_associated_token_account::get_associated_token_address(
-            owner, mint,
-        ))?
-        .as_token_account()
+    ) -> Result<Ref<'_, TokenAccount>, ProgramError> {
+        let (address, _) = find_program_address(
+            &[owner, &pinocchio_token::ID, mint],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        );
+        self.assert_key(&address)?.as_token_account()
+    }
+
+    fn total_supply(&self) -> Result<u64, ProgramError> {
+        Ok(self.as_mint()?.supply())
+    }
+
+    fn decimals(&self) -> Result<u8, ProgramError> {
+        Ok(self.as_mint()?.decimals())
+    }
+
+    fn is_initialized(&self) -> Result<bool, ProgramError> {
+        Ok(self.as_mint()?.is_initialized())
+    }
+
+    fn assert_is_native_mint(&self) -> Result<&Self, ProgramError> {
+        self.assert_key(&NATIVE_MINT_ID)
+    }
+}
+
+impl SplTokenCpi for AccountInfo {
+    fn mint_to(
+        &self,
+        mint: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+        decimals: u8,
+    ) -> Result<&Self, ProgramError> {
+        self.mint_to_signed(mint, authority, amount, decimals, &[])
+    }
+
+    fn mint_to_signed(
+        &self,
+        mint: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+        decimals: u8,
+        signers: &[Signer],
+    ) -> Result<&Self, ProgramError> {
+        MintToChecked {
+            mint,
+            account: self,
+            mint_authority: authority,
+            amount,
+            decimals,
+        }
+        .invoke_signed(signers)?;
+        Ok(self)
+    }
+
+    fn burn(
+        &self,
+        mint: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+    ) -> Result<&Self, ProgramError> {
+        self.burn_signed(mint, authority, amount, &[])
+    }
+
+    fn burn_signed(
+        &self,
+        mint: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+        signers: &[Signer],
+    ) -> Result<&Self, ProgramError> {
+        Burn {
+            account: self,
+            mint,
+            authority,
+            amount,
+        }
+        .invoke_signed(signers)?;
+        Ok(self)
+    }
+
+    fn spl_transfer(
+        &self,
+        mint: &AccountInfo,
+        destination: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+        decimals: u8,
+    ) -> Result<&Self, ProgramError> {
+        self.spl_transfer_signed(mint, destination, authority, amount, decimals, &[])
+    }
+
+    fn spl_transfer_signed(
+        &self,
+        mint: &AccountInfo,
+        destination: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+        decimals: u8,
+        signers: &[Signer],
+    ) -> Result<&Self, ProgramError> {
+        TransferChecked {
+            from: self,
+            mint,
+            to: destination,
+            authority,
+            amount,
+            decimals,
+        }
+        .invoke_signed(signers)?;
+        Ok(self)
+    }
+
+    fn approve(
+        &self,
+        delegate: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+    ) -> Result<&Self, ProgramError> {
+        self.approve_signed(delegate, authority, amount, &[])
+    }
+
+    fn approve_signed(
+        &self,
+        delegate: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+        signers: &[Signer],
+    ) -> Result<&Self, ProgramError> {
+        Approve {
+            source: self,
+            delegate,
+            authority,
+            amount,
+        }
+        .invoke_signed(signers)?;
+        Ok(self)
+    }
+}
+
+#[cfg(feature = "spl2022")]
+impl AsSplToken2022 for AccountInfo {
+    fn as_mint_2022(&self) -> Result<spl_token_2022::state::Mint, ProgramError> {
+        self.assert_owner(&TOKEN_2022_PROGRAM_ID)?;
+        StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&self.try_borrow_data()?)
+            .map(|state| state.base)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    fn as_token_account_2022(&self) -> Result<spl_token_2022::state::Account, ProgramError> {
+        self.assert_owner(&TOKEN_2022_PROGRAM_ID)?;
+        StateWithExtensions::<spl_token_2022::state::Account>::unpack(&self.try_borrow_data()?)
+            .map(|state| state.base)
+            .map_err(|_| ProgramError::InvalidAccountData)
+    }
+
+    fn as_associated_token_account_2022(
+        &self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<spl_token_2022::state::Account, ProgramError> {
+        let (address, _) = find_program_address(
+            &[owner, &TOKEN_2022_PROGRAM_ID, mint],
+            &ASSOCIATED_TOKEN_PROGRAM_ID,
+        );
+        self.assert_key(&address)?.as_token_account_2022()
+    }
+}
+
+impl MintValidation for Mint {
+    fn assert_fungible(&self) -> Result<&Self, ProgramError> {
+        if classify_mint(self).ne(&MintState::Fungible) {
+            msg!("Mint is not fungible");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(self)
+    }
+
+    fn assert_nonfungible(&self) -> Result<&Self, ProgramError> {
+        if classify_mint(self).ne(&MintState::NonFungible) {
+            msg!("Mint is not non-fungible");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(self)
+    }
+}
+
+impl MintCapValidation for Mint {
+    fn assert_max_supply(
+        &self,
+        amount_to_mint: u64,
+        max_supply: u64,
+    ) -> Result<&Self, ProgramError> {
+        let new_supply = self
+            .supply()
+            .checked_add(amount_to_mint)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        if new_supply.gt(&max_supply) {
+            msg!(
+                "Mint would exceed max supply (supply, amount, max): {:?}, {:?}, {:?}",
+                self.supply(),
+                amount_to_mint,
+                max_supply
+            );
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(self)
     }
 }
 
@@ -395,3 +1228,430 @@ impl AccountValidation for spl_token::state::Account {
         panic!("not implemented")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestAccount;
+    use bytemuck::Zeroable;
+
+    const PROGRAM_ID: Pubkey = [7; 32];
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Pod, Zeroable)]
+    struct Counter {
+        value: u64,
+    }
+
+    impl Discriminator for Counter {
+        fn discriminator() -> u8 {
+            2
+        }
+    }
+
+    #[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+    struct Profile {
+        name: String,
+    }
+
+    impl Discriminator for Profile {
+        fn discriminator() -> u8 {
+            3
+        }
+    }
+
+    fn profile_data(name: &str, padding: usize) -> Vec<u8> {
+        let mut data = vec![Profile::discriminator()];
+        data.extend(Profile { name: name.into() }.try_to_vec().unwrap());
+        data.resize(data.len() + padding, 0);
+        data
+    }
+
+    fn counter_data(value: u64) -> Vec<u8> {
+        let mut data = vec![0; POD_BODY_OFFSET];
+        data[0] = Counter::discriminator();
+        data.extend_from_slice(&value.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn assert_min_size_counts_padded_discriminator() {
+        let mut account = TestAccount::new([1; 32], PROGRAM_ID, 0, &counter_data(5));
+        assert!(account.info().assert_min_size::<Counter>().is_ok());
+
+        let mut account = TestAccount::new([1; 32], PROGRAM_ID, 0, &counter_data(5)[..15]);
+        assert_eq!(
+            account.info().assert_min_size::<Counter>().err(),
+            Some(ProgramError::AccountDataTooSmall)
+        );
+    }
+
+    #[test]
+    fn as_account_mut_writes_through_to_account_data() {
+        let mut account = TestAccount::new([1; 32], PROGRAM_ID, 0, &counter_data(5));
+        let info = account.info();
+
+        info.as_account_mut::<Counter>(&PROGRAM_ID).unwrap().value += 1;
+
+        assert_eq!(*info.try_borrow_data().unwrap(), counter_data(6)[..]);
+    }
+
+    #[test]
+    fn as_account_mut_rejects_invalid_accounts() {
+        let mut account = TestAccount::new([1; 32], [8; 32], 0, &counter_data(5));
+        assert_eq!(
+            account.info().as_account_mut::<Counter>(&PROGRAM_ID).err(),
+            Some(ProgramError::InvalidAccountOwner)
+        );
+
+        let mut account = TestAccount::new([1; 32], PROGRAM_ID, 0, &counter_data(5)[..12]);
+        assert_eq!(
+            account.info().as_account_mut::<Counter>(&PROGRAM_ID).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+
+        let mut account = TestAccount::new([1; 32], PROGRAM_ID, 0, &counter_data(5)).readonly();
+        assert!(account
+            .info()
+            .as_account_mut::<Counter>(&PROGRAM_ID)
+            .is_err());
+    }
+
+    #[test]
+    fn as_account_ignores_trailing_space() {
+        let mut account = TestAccount::new([1; 32], PROGRAM_ID, 0, &profile_data("ab", 10));
+        assert_eq!(
+            account.info().as_account::<Profile>(&PROGRAM_ID),
+            Ok(Profile { name: "ab".into() })
+        );
+    }
+
+    #[test]
+    fn save_account_keeps_padded_allocation() {
+        let mut account = TestAccount::new([1; 32], PROGRAM_ID, 0, &profile_data("abc", 10));
+        let info = account.info();
+
+        let profile = Profile { name: "a".into() };
+        info.save_account(&PROGRAM_ID, &profile).unwrap();
+
+        assert_eq!(info.data_len(), profile_data("abc", 10).len());
+        assert_eq!(*info.try_borrow_data().unwrap(), profile_data("a", 12)[..]);
+        assert_eq!(info.as_account::<Profile>(&PROGRAM_ID), Ok(profile));
+        assert_eq!(
+            info.save_account_if_changed(&PROGRAM_ID, &Profile { name: "a".into() }),
+            Ok(false)
+        );
+    }
+
+    fn rent() -> Rent {
+        Rent {
+            lamports_per_byte_year: 3480,
+            exemption_threshold: 2.0,
+            burn_percent: 50,
+        }
+    }
+
+    #[test]
+    fn grow_account_reallocs_when_rent_exempt() {
+        let mut account =
+            TestAccount::new([1; 32], PROGRAM_ID, rent().minimum_balance(64), &[1; 16]);
+        let info = account.info();
+
+        grow_account(&info, 64, &rent()).unwrap();
+
+        assert_eq!(info.data_len(), 64);
+        assert_eq!(info.try_borrow_data().unwrap()[..16], [1; 16]);
+    }
+
+    #[test]
+    fn grow_account_fails_on_rent_shortfall() {
+        let lamports = rent().minimum_balance(64) - 1;
+        let mut account = TestAccount::new([1; 32], PROGRAM_ID, lamports, &[1; 16]);
+        let info = account.info();
+
+        assert_eq!(
+            grow_account(&info, 64, &rent()),
+            Err(ProgramError::AccountNotRentExempt)
+        );
+        assert_eq!(info.data_len(), 16);
+    }
+
+    #[test]
+    fn resize_account_never_shrinks() {
+        let mut account = TestAccount::new([1; 32], PROGRAM_ID, 5, &[1; 16]);
+        let info = account.info();
+
+        resize_account(&info, 16).unwrap();
+        resize_account(&info, 4).unwrap();
+
+        assert_eq!(info.data_len(), 16);
+        assert_eq!(info.lamports(), 5);
+    }
+
+    #[test]
+    fn save_account_roundtrips_same_size_value() {
+        let mut account = TestAccount::new([1; 32], PROGRAM_ID, 0, &profile_data("abc", 0));
+        let info = account.info();
+
+        let profile = Profile { name: "xyz".into() };
+        info.save_account(&PROGRAM_ID, &profile).unwrap();
+
+        assert_eq!(*info.try_borrow_data().unwrap(), profile_data("xyz", 0)[..]);
+        assert_eq!(info.as_account::<Profile>(&PROGRAM_ID), Ok(profile));
+    }
+
+    fn mint_data(supply: u64, decimals: u8) -> [u8; Mint::LEN] {
+        let mut data = [0; Mint::LEN];
+        data[36..44].copy_from_slice(&supply.to_le_bytes());
+        data[44] = decimals;
+        data[45] = 1;
+        data
+    }
+
+    #[test]
+    fn mint_validation_checks_fungibility() {
+        let mut nft = TestAccount::new([1; 32], pinocchio_token::ID, 0, &mint_data(1, 0));
+        let info = nft.info();
+        let mint = Mint::from_account_info(&info).unwrap();
+        assert!(mint.assert_nonfungible().is_ok());
+        assert_eq!(
+            mint.assert_fungible().err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+
+        let mut token = TestAccount::new([2; 32], pinocchio_token::ID, 0, &mint_data(1, 6));
+        let info = token.info();
+        let mint = Mint::from_account_info(&info).unwrap();
+        assert!(mint.assert_fungible().is_ok());
+        assert_eq!(
+            mint.assert_nonfungible().err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn mint_shortcuts_read_mint_state() {
+        let mut mint = TestAccount::new([1; 32], pinocchio_token::ID, 0, &mint_data(500, 6));
+        let info = mint.info();
+
+        assert_eq!(info.total_supply(), Ok(500));
+        assert_eq!(info.decimals(), Ok(6));
+        assert_eq!(info.is_initialized(), Ok(true));
+    }
+
+    #[test]
+    fn mint_shortcuts_reject_non_mint_accounts() {
+        let mut foreign = TestAccount::new([1; 32], PROGRAM_ID, 0, &mint_data(500, 6));
+        assert!(foreign.info().total_supply().is_err());
+
+        let mut short = TestAccount::new([2; 32], pinocchio_token::ID, 0, &[0; 40]);
+        assert!(short.info().decimals().is_err());
+    }
+
+    #[test]
+    fn assert_is_native_mint_checks_address() {
+        let mut native = TestAccount::new(NATIVE_MINT_ID, pinocchio_token::ID, 0, &mint_data(0, 9));
+        assert!(native.info().assert_is_native_mint().is_ok());
+
+        let mut other = TestAccount::new([1; 32], pinocchio_token::ID, 0, &mint_data(0, 9));
+        assert!(other.info().assert_is_native_mint().is_err());
+    }
+
+    #[test]
+    fn assert_max_supply_allows_minting_up_to_cap() {
+        let data = mint_data(90, 0);
+        let mint = unsafe { Mint::from_bytes(&data) };
+
+        assert!(mint.assert_max_supply(10, 100).is_ok());
+        assert_eq!(
+            mint.assert_max_supply(11, 100).err(),
+            Some(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            mint.assert_max_supply(u64::MAX, u64::MAX).err(),
+            Some(ProgramError::ArithmeticOverflow)
+        );
+    }
+
+    #[test]
+    fn assert_not_empty_reports_uninitialized_account() {
+        let mut empty = TestAccount::new([1; 32], PROGRAM_ID, 0, &[]);
+        assert_eq!(
+            empty.info().assert_not_empty().err(),
+            Some(ProgramError::UninitializedAccount)
+        );
+
+        let mut full = TestAccount::new([2; 32], PROGRAM_ID, 0, &[1]);
+        assert!(full.info().assert_not_empty().is_ok());
+    }
+
+    #[test]
+    fn lamport_bounds_are_inclusive_below_and_exclusive_above() {
+        let mut account = TestAccount::new([1; 32], PROGRAM_ID, 100, &[]);
+        let info = account.info();
+
+        assert!(info.assert_lamports_gte(100).is_ok());
+        assert_eq!(
+            info.assert_lamports_gte(101).err(),
+            Some(ProgramError::InsufficientFunds)
+        );
+        assert!(info.assert_lamports_lt(101).is_ok());
+        assert_eq!(
+            info.assert_lamports_lt(100).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+    struct ProfileV1 {
+        name: String,
+    }
+
+    #[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq)]
+    struct ProfileV2 {
+        name: String,
+        level: u64,
+    }
+
+    impl Discriminator for ProfileV1 {
+        fn discriminator() -> u8 {
+            3
+        }
+    }
+
+    impl AccountVersion for ProfileV1 {
+        fn version() -> u8 {
+            1
+        }
+    }
+
+    impl Discriminator for ProfileV2 {
+        fn discriminator() -> u8 {
+            3
+        }
+    }
+
+    impl AccountVersion for ProfileV2 {
+        fn version() -> u8 {
+            2
+        }
+    }
+
+    #[test]
+    fn migrate_account_rewrites_old_layout_as_new() {
+        let mut data = vec![3, 1];
+        data.extend(ProfileV1 { name: "abc".into() }.try_to_vec().unwrap());
+        data.extend([0; 16]);
+        let mut account = TestAccount::new([1; 32], PROGRAM_ID, 0, &data);
+        let info = account.info();
+
+        let migrated = info
+            .migrate_account::<ProfileV1, ProfileV2, _>(&PROGRAM_ID, |old| ProfileV2 {
+                name: old.name,
+                level: 5,
+            })
+            .unwrap();
+
+        let expected = ProfileV2 {
+            name: "abc".into(),
+            level: 5,
+        };
+        assert_eq!(migrated, expected);
+        assert_eq!(
+            info.as_versioned_account::<ProfileV2>(&PROGRAM_ID),
+            Ok(expected)
+        );
+        assert_eq!(
+            info.as_versioned_account::<ProfileV1>(&PROGRAM_ID).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn close_into_program_authority_rejects_foreign_program_data() {
+        let mut account = TestAccount::new([1; 32], PROGRAM_ID, 10, &[1]);
+        let mut program_data = TestAccount::new([2; 32], PROGRAM_ID, 0, &[3, 0, 0, 0]);
+        let mut authority = TestAccount::new([3; 32], [0; 32], 0, &[]).signer();
+        let (account, program_data, authority) =
+            (account.info(), program_data.info(), authority.info());
+
+        assert_eq!(
+            account
+                .close_into_program_authority(&program_data, &authority, &PROGRAM_ID)
+                .err(),
+            Some(ProgramError::InvalidAccountOwner)
+        );
+        assert_eq!(account.lamports(), 10);
+    }
+
+    #[test]
+    fn assert_not_signer_and_not_writable() {
+        let mut plain = TestAccount::new([1; 32], PROGRAM_ID, 0, &[]).readonly();
+        let info = plain.info();
+        assert!(info.assert_not_signer().is_ok());
+        assert!(info.assert_not_writable().is_ok());
+
+        let mut signer = TestAccount::new([2; 32], PROGRAM_ID, 0, &[]).signer();
+        let info = signer.info();
+        assert_eq!(
+            info.assert_not_signer().err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            info.assert_not_writable().err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn close_to_token_account_moves_lamports_into_vault() {
+        let mut account = TestAccount::new([1; 32], PROGRAM_ID, 10, &[1, 2]);
+        let mut vault = TestAccount::new([2; 32], pinocchio_token::ID, 5, &[0; 165]);
+        let mut token_program = TestAccount::new(pinocchio_token::ID, [0; 32], 1, &[])
+            .readonly()
+            .executable();
+        let (account, vault, token_program) = (account.info(), vault.info(), token_program.info());
+
+        account
+            .close_to_token_account(&vault, &token_program)
+            .unwrap();
+
+        assert_eq!(vault.lamports(), 15);
+        assert_eq!(account.lamports(), 0);
+        assert_eq!(account.data_len(), 0);
+    }
+
+    #[test]
+    fn close_to_token_account_rejects_vault_not_owned_by_token_program() {
+        let mut account = TestAccount::new([1; 32], PROGRAM_ID, 10, &[1, 2]);
+        let mut vault = TestAccount::new([2; 32], PROGRAM_ID, 5, &[0; 165]);
+        let mut token_program = TestAccount::new(pinocchio_token::ID, [0; 32], 1, &[])
+            .readonly()
+            .executable();
+        let (account, vault, token_program) = (account.info(), vault.info(), token_program.info());
+
+        assert_eq!(
+            account.close_to_token_account(&vault, &token_program).err(),
+            Some(ProgramError::InvalidAccountOwner)
+        );
+        assert_eq!(account.lamports(), 10);
+        assert_eq!(vault.lamports(), 5);
+    }
+
+    #[test]
+    fn close_with_discriminator_zero_wipes_discriminator_and_moves_lamports() {
+        let mut account = TestAccount::new([1; 32], PROGRAM_ID, 10, &counter_data(7));
+        let mut destination = TestAccount::new([2; 32], [0; 32], 5, &[]);
+        let (account, destination) = (account.info(), destination.info());
+
+        account.close_with_discriminator_zero(&destination).unwrap();
+
+        assert_eq!(destination.lamports(), 15);
+        assert_eq!(account.lamports(), 0);
+        assert_eq!(account.data_len(), 0);
+
+        // Growing without zeroing exposes the bytes left behind in the account's memory.
+        account.realloc(1, false).unwrap();
+        assert_eq!(account.try_borrow_data().unwrap()[0], 0);
+    }
+}
@@ -1,6 +1,71 @@
 #[macro_export]
 macro_rules! account {
     ($discriminator_name:ident, $struct_name:ident) => {
+        $crate::account!($discriminator_name, $struct_name, skip_validation);
+        $crate::impl_account_validation!($struct_name);
+    };
+    // Additionally generates `assert_<field>_gte`, `assert_<field>_lte` and `assert_<field>_eq`
+    // for each listed field, e.g. `account!(MyAccount, Counter, numeric_fields = [value: u64])`,
+    // and with the `testing` feature a `summary` of the listed fields.
+    ($discriminator_name:ident, $struct_name:ident, numeric_fields = [$($field:ident : $field_type:ty),* $(,)?]) => {
+        $crate::account!($discriminator_name, $struct_name);
+        $crate::impl_account_summary!($struct_name, [$($field),*]);
+
+        $crate::paste::paste! {
+            impl $struct_name {
+                $(
+                    pub fn [<assert_ $field _gte>](
+                        &self,
+                        min: $field_type,
+                    ) -> Result<&Self, pinocchio::program_error::ProgramError> {
+                        if self.$field.lt(&min) {
+                            pinocchio::msg!(
+                                "{} is below minimum (min, actual): {:?}, {:?}",
+                                stringify!($field),
+                                min,
+                                self.$field
+                            );
+                            return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
+                        }
+                        Ok(self)
+                    }
+
+                    pub fn [<assert_ $field _lte>](
+                        &self,
+                        max: $field_type,
+                    ) -> Result<&Self, pinocchio::program_error::ProgramError> {
+                        if self.$field.gt(&max) {
+                            pinocchio::msg!(
+                                "{} is above maximum (max, actual): {:?}, {:?}",
+                                stringify!($field),
+                                max,
+                                self.$field
+                            );
+                            return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
+                        }
+                        Ok(self)
+                    }
+
+                    pub fn [<assert_ $field _eq>](
+                        &self,
+                        expected: $field_type,
+                    ) -> Result<&Self, pinocchio::program_error::ProgramError> {
+                        if self.$field.ne(&expected) {
+                            pinocchio::msg!(
+                                "{} mismatch (expected, actual): {:?}, {:?}",
+                                stringify!($field),
+                                expected,
+                                self.$field
+                            );
+                            return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
+                        }
+                        Ok(self)
+                    }
+                )*
+            }
+        }
+    };
+    ($discriminator_name:ident, $struct_name:ident, skip_validation) => {
         impl $struct_name
         where
             Self: borsh::BorshSerialize,
@@ -8,6 +73,22 @@ macro_rules! account {
             pub fn to_bytes(&self) -> Vec<u8> {
                 borsh::to_vec(self).unwrap()
             }
+
+            /// Deserializes the account from raw on-disk bytes, including the discriminator.
+            /// Trailing space after the body is ignored.
+            pub fn from_data_slice(
+                data: &[u8],
+            ) -> Result<Self, pinocchio::program_error::ProgramError> {
+                let (discriminator, data) = data
+                    .split_first()
+                    .ok_or(pinocchio::program_error::ProgramError::InvalidAccountData)?;
+                if discriminator.ne(&<Self as $crate::Discriminator>::discriminator()) {
+                    return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
+                }
+                <Self as borsh::BorshDeserialize>::deserialize(&mut &data[..]).or(Err(
+                    pinocchio::program_error::ProgramError::InvalidAccountData,
+                ))
+            }
         }
 
         impl $crate::Discriminator for $struct_name {
@@ -16,6 +97,336 @@ macro_rules! account {
             }
         }
 
+        // Converts an account owned by the given program, e.g.
+        // `let counter = Counter::try_from((info, program_id))?;`.
+        impl
+            TryFrom<(
+                &pinocchio::account_info::AccountInfo,
+                &pinocchio::pubkey::Pubkey,
+            )> for $struct_name
+        {
+            type Error = pinocchio::program_error::ProgramError;
+
+            fn try_from(
+                (info, program_id): (
+                    &pinocchio::account_info::AccountInfo,
+                    &pinocchio::pubkey::Pubkey,
+                ),
+            ) -> Result<Self, pinocchio::program_error::ProgramError> {
+                $crate::AccountInfoValidation::assert_owner(info, program_id)?;
+                Self::from_data_slice(&info.try_borrow_data()?)
+            }
+        }
+    };
+}
+
+/// Same as `account!`, but with a 2-byte discriminator for programs with more than 256 account
+/// types. The discriminator enum must be `#[repr(u16)]` and derive `IntoPrimitive`.
+#[macro_export]
+macro_rules! account16 {
+    ($discriminator_name:ident, $struct_name:ident) => {
+        $crate::impl_wide_account!($discriminator_name, $struct_name, u16, Discriminator16);
+    };
+}
+
+/// Same as `account!`, but with a 4-byte discriminator. The discriminator enum must be
+/// `#[repr(u32)]` and derive `IntoPrimitive`.
+#[macro_export]
+macro_rules! account32 {
+    ($discriminator_name:ident, $struct_name:ident) => {
+        $crate::impl_wide_account!($discriminator_name, $struct_name, u32, Discriminator32);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_wide_account {
+    ($discriminator_name:ident, $struct_name:ident, $width:ty, $discriminator_trait:ident) => {
+        impl $struct_name
+        where
+            Self: borsh::BorshSerialize,
+        {
+            pub fn to_bytes(&self) -> Vec<u8> {
+                borsh::to_vec(self).unwrap()
+            }
+
+            /// Deserializes the account from raw on-disk bytes, including the discriminator.
+            /// Trailing space after the body is ignored.
+            pub fn from_data_slice(
+                data: &[u8],
+            ) -> Result<Self, pinocchio::program_error::ProgramError> {
+                if !<Self as $crate::AccountDiscriminator>::discriminator_matches(data) {
+                    return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
+                }
+                let mut data = &data[<$width as $crate::DiscriminatorWidth>::LEN..];
+                <Self as borsh::BorshDeserialize>::deserialize(&mut data).or(Err(
+                    pinocchio::program_error::ProgramError::InvalidAccountData,
+                ))
+            }
+        }
+
+        impl $crate::$discriminator_trait for $struct_name {
+            fn discriminator() -> $width {
+                $discriminator_name::$struct_name.into()
+            }
+        }
+
+        impl $crate::AccountDiscriminator for $struct_name {
+            type Width = $width;
+
+            fn account_discriminator() -> $width {
+                <Self as $crate::$discriminator_trait>::discriminator()
+            }
+        }
+
+        // Converts an account owned by the given program, e.g.
+        // `let counter = Counter::try_from((info, program_id))?;`.
+        impl
+            TryFrom<(
+                &pinocchio::account_info::AccountInfo,
+                &pinocchio::pubkey::Pubkey,
+            )> for $struct_name
+        {
+            type Error = pinocchio::program_error::ProgramError;
+
+            fn try_from(
+                (info, program_id): (
+                    &pinocchio::account_info::AccountInfo,
+                    &pinocchio::pubkey::Pubkey,
+                ),
+            ) -> Result<Self, pinocchio::program_error::ProgramError> {
+                $crate::AccountInfoValidation::assert_owner(info, program_id)?;
+                Self::from_data_slice(&info.try_borrow_data()?)
+            }
+        }
+        $crate::impl_account_validation!($struct_name);
+    };
+}
+
+/// Fails compilation if any two of the listed types share a discriminator value, e.g.
+/// `check_discriminators!(MyAccount, [Counter, Profile]);`.
+#[macro_export]
+macro_rules! check_discriminators {
+    (@pairs $discriminator_name:ident $(,)?) => {};
+    (@pairs $discriminator_name:ident, $first:ident $(, $rest:ident)*) => {
+        $(
+            assert!(
+                ($discriminator_name::$first as u64) != ($discriminator_name::$rest as u64),
+                concat!(
+                    "discriminator collision: ",
+                    stringify!($first),
+                    " and ",
+                    stringify!($rest),
+                    " map to the same value"
+                )
+            );
+        )*
+        $crate::check_discriminators!(@pairs $discriminator_name $(, $rest)*);
+    };
+    ($discriminator_name:ident, [$($struct_name:ident),* $(,)?]) => {
+        const _: () = {
+            $crate::check_discriminators!(@pairs $discriminator_name $(, $struct_name)*);
+        };
+    };
+}
+
+/// Generates a `summary` method formatting the listed numeric fields as a comma-separated string,
+/// e.g. `value: 5, bump: 254`, for debugging in tests. Only available with the `testing` feature,
+/// since it allocates.
+#[cfg(feature = "testing")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_account_summary {
+    ($struct_name:ident, [$($field:ident),*]) => {
+        impl $struct_name {
+            pub fn summary(&self) -> String {
+                <[String]>::join(
+                    &[$(format!("{}: {}", stringify!($field), self.$field)),*],
+                    ", ",
+                )
+            }
+        }
+    };
+}
+
+#[cfg(not(feature = "testing"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_account_summary {
+    ($struct_name:ident, [$($field:ident),*]) => {};
+}
+
+/// Same as [`account!`], for accounts whose layout carries a version byte after the
+/// discriminator. Previous layouts of the same account can share its discriminator variant, so
+/// they can be read and migrated with `as_versioned_account` and `migrate_account`.
+///
+/// ```ignore
+/// versioned_account!(MyAccount, Counter, 2);
+/// versioned_account!(MyAccount::Counter, CounterV1, 1);
+/// ```
+#[macro_export]
+macro_rules! versioned_account {
+    ($discriminator_name:ident, $struct_name:ident, $version:expr) => {
+        $crate::versioned_account!($discriminator_name::$struct_name, $struct_name, $version);
+    };
+    ($discriminator_name:ident :: $variant:ident, $struct_name:ident, $version:expr) => {
+        impl $struct_name
+        where
+            Self: borsh::BorshSerialize,
+        {
+            pub fn to_bytes(&self) -> Vec<u8> {
+                borsh::to_vec(self).unwrap()
+            }
+        }
+
+        impl $crate::Discriminator for $struct_name {
+            fn discriminator() -> u8 {
+                $discriminator_name::$variant.into()
+            }
+        }
+
+        impl $crate::AccountVersion for $struct_name {
+            fn version() -> u8 {
+                $version
+            }
+        }
+
+        $crate::impl_account_validation!($struct_name);
+    };
+}
+
+/// Implements [`crate::AnchorDiscriminator`] for an account type of an Anchor program, so it can
+/// be loaded with `as_anchor_account`. Anchor zero-copy accounts, whose `Pod` body follows the
+/// 8-byte discriminator, additionally implement [`crate::AccountDeserialize`] with `zero_copy`.
+///
+/// ```ignore
+/// anchor_account!(Counter);
+/// anchor_account!(Vault, zero_copy);
+/// ```
+#[macro_export]
+macro_rules! anchor_account {
+    ($struct_name:ident) => {
+        impl $crate::AnchorDiscriminator for $struct_name {
+            fn discriminator() -> [u8; 8] {
+                const DISCRIMINATOR: [u8; 8] =
+                    $crate::anchor_discriminator(concat!("account:", stringify!($struct_name)));
+                DISCRIMINATOR
+            }
+        }
+    };
+    ($struct_name:ident, zero_copy) => {
+        $crate::anchor_account!($struct_name);
+
+        impl $crate::AccountDeserialize for $struct_name {
+            fn try_from_bytes(
+                data: &[u8],
+            ) -> Result<&Self, pinocchio::program_error::ProgramError> {
+                if !$crate::anchor_discriminator_matches::<Self>(data) {
+                    return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
+                }
+                $crate::try_pod_body::<Self>(data)
+            }
+
+            fn try_from_bytes_mut(
+                data: &mut [u8],
+            ) -> Result<&mut Self, pinocchio::program_error::ProgramError> {
+                if !$crate::anchor_discriminator_matches::<Self>(data) {
+                    return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
+                }
+                $crate::try_pod_body_mut::<Self>(data)
+            }
+        }
+    };
+}
+
+/// Defines a zero-copy account struct, deriving `Pod` and `Zeroable` with a `#[repr(C)]` layout.
+///
+/// The struct must derive `Clone` and `Copy`, and must not declare its own `repr`.
+///
+/// ```ignore
+/// account_zero_copy!(
+///     MyAccount,
+///     #[derive(Clone, Copy, Debug)]
+///     pub struct Counter {
+///         pub value: u64,
+///     }
+/// );
+/// ```
+#[macro_export]
+macro_rules! account_zero_copy {
+    (
+        $discriminator_name:ident,
+        $(#[$attr:meta])*
+        $vis:vis struct $struct_name:ident { $($fields:tt)* }
+    ) => {
+        $(#[$attr])*
+        #[repr(C)]
+        #[derive(bytemuck::Pod, bytemuck::Zeroable)]
+        $vis struct $struct_name { $($fields)* }
+
+        const _: () = assert!(
+            std::mem::align_of::<$struct_name>() <= 8,
+            concat!(stringify!($struct_name), " must have an alignment of at most 8 bytes"),
+        );
+
+        $crate::pod_account!($discriminator_name, $struct_name);
+    };
+}
+
+/// Implements a bytemuck-based account for an existing `Pod` struct, skipping borsh entirely.
+/// Accounts are read in place via `AccountDeserialize`, without any heap allocation.
+///
+/// ```ignore
+/// pod_account!(MyAccount, Counter);
+/// ```
+#[macro_export]
+macro_rules! pod_account {
+    ($discriminator_name:ident, $struct_name:ident) => {
+        const _: fn() = || {
+            fn assert_pod<T: bytemuck::Pod>() {}
+            assert_pod::<$struct_name>();
+        };
+
+        impl $struct_name {
+            pub fn to_bytes(&self) -> &[u8] {
+                bytemuck::bytes_of(self)
+            }
+        }
+
+        impl $crate::Discriminator for $struct_name {
+            fn discriminator() -> u8 {
+                $discriminator_name::$struct_name.into()
+            }
+        }
+
+        impl $crate::AccountDeserialize for $struct_name {
+            fn try_from_bytes(
+                data: &[u8],
+            ) -> Result<&Self, pinocchio::program_error::ProgramError> {
+                if !<Self as $crate::AccountDiscriminator>::discriminator_matches(data) {
+                    return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
+                }
+                $crate::try_pod_body::<Self>(data)
+            }
+
+            fn try_from_bytes_mut(
+                data: &mut [u8],
+            ) -> Result<&mut Self, pinocchio::program_error::ProgramError> {
+                if !<Self as $crate::AccountDiscriminator>::discriminator_matches(data) {
+                    return Err(pinocchio::program_error::ProgramError::InvalidAccountData);
+                }
+                $crate::try_pod_body_mut::<Self>(data)
+            }
+        }
+
+        $crate::impl_account_validation!($struct_name);
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_account_validation {
+    ($struct_name:ident) => {
         impl $crate::AccountValidation for $struct_name {
             fn assert<F>(
                 &self,
@@ -121,6 +532,29 @@ macro_rules! error {
     };
 }
 
+/// Same as [`error!`], additionally generating `to_http_status_code` on the error enum for
+/// off-chain tooling. Variants without an explicit mapping resolve to 500.
+///
+/// ```ignore
+/// error_with_http!(MyError, { Dummy = 400, InvalidAuth = 401 });
+/// ```
+#[macro_export]
+macro_rules! error_with_http {
+    ($struct_name:ident, { $($variant:ident = $status:literal),* $(,)? }) => {
+        $crate::error!($struct_name);
+
+        impl $struct_name {
+            pub fn to_http_status_code(&self) -> u16 {
+                #[allow(unreachable_patterns)]
+                match self {
+                    $($struct_name::$variant => $status,)*
+                    _ => 500,
+                }
+            }
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! event {
     ($struct_name:ident) => {
@@ -131,6 +565,19 @@ macro_rules! event {
             pub fn to_bytes(&self) -> Vec<u8> {
                 borsh::to_vec(self).unwrap()
             }
+
+            /// Serializes the event into a stack buffer, returning the buffer and the number of
+            /// bytes written. `N` must be at least the borsh-serialized size of the event.
+            pub fn to_bytes_stack<const N: usize>(
+                &self,
+            ) -> Result<([u8; N], usize), pinocchio::program_error::ProgramError> {
+                let mut buffer = [0u8; N];
+                let mut writer = buffer.as_mut_slice();
+                borsh::BorshSerialize::serialize(self, &mut writer)
+                    .or(Err(pinocchio::program_error::ProgramError::InvalidArgument))?;
+                let len = N - writer.len();
+                Ok((buffer, len))
+            }
         }
 
         impl $crate::Loggable for $struct_name {
@@ -142,6 +589,22 @@ macro_rules! event {
                 pinocchio::program::set_return_data(self.to_bytes().as_slice());
             }
         }
+
+        impl $crate::ToLogBytes for $struct_name {
+            fn to_log_bytes(&self) -> Vec<u8> {
+                self.to_bytes()
+            }
+        }
+    };
+    ($struct_name:ident, schema) => {
+        $crate::event!($struct_name);
+
+        impl $struct_name {
+            /// Returns a JSON object describing the event's fields. Requires `#[schema]`.
+            pub fn schema() -> &'static str {
+                <Self as $crate::EventSchema>::SCHEMA
+            }
+        }
     };
 }
 
@@ -183,6 +646,7 @@ macro_rules! borsh_instruction {
             Self: borsh::BorshSerialize,
             Self: borsh::BorshDeserialize,
         {
+            #[allow(dead_code)]
             pub fn try_from_bytes(
                 data: &[u8],
             ) -> Result<Self, pinocchio::program_error::ProgramError> {
@@ -199,5 +663,242 @@ macro_rules! borsh_instruction {
                 .concat()
             }
         }
+
+        impl $crate::BorshInstructionData for $struct_name {
+            fn to_instruction_data(&self) -> Vec<u8> {
+                self.to_bytes()
+            }
+        }
+    };
+    // Additionally implements `Debug` printing the listed fields by name, for instruction structs
+    // that do not derive it, e.g.
+    // `borsh_instruction!(MyInstruction, Deposit, debug_fields = [amount])`.
+    ($discriminator_name:ident, $struct_name:ident, debug_fields = [$($field:ident),* $(,)?]) => {
+        $crate::borsh_instruction!($discriminator_name, $struct_name);
+
+        impl core::fmt::Debug for $struct_name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_struct(stringify!($struct_name))
+                    $(.field(stringify!($field), &self.$field))*
+                    .finish()
+            }
+        }
+    };
+}
+
+/// Returns the number of bytes to allocate for an account of the given `BorshSize` type,
+/// including its discriminator. Used by `AsAccount::create_account`.
+#[macro_export]
+macro_rules! account_size {
+    ($struct_name:ty) => {
+        <<$struct_name as $crate::AccountDiscriminator>::Width as $crate::DiscriminatorWidth>::LEN
+            + <$struct_name as $crate::BorshSize>::MAX_SIZE
+    };
+}
+
+/// Logs a message only when the calling crate is built with `debug_assertions`, compiling to
+/// nothing in release builds.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        #[cfg(debug_assertions)]
+        {
+            pinocchio::msg!($($arg)*);
+        }
+    };
+}
+
+/// Declares the number of accounts an instruction expects, generating an `ACCOUNTS_COUNT`
+/// constant and a `check_accounts_count` guard on the instruction struct.
+#[macro_export]
+macro_rules! accounts_count {
+    ($struct_name:ident, $count:expr) => {
+        impl $struct_name {
+            pub const ACCOUNTS_COUNT: usize = $count;
+
+            pub fn check_accounts_count(
+                accounts: &[pinocchio::account_info::AccountInfo],
+            ) -> Result<(), pinocchio::program_error::ProgramError> {
+                $crate::expect_accounts(accounts, Self::ACCOUNTS_COUNT)
+            }
+        }
+    };
+}
+
+/// Logs `key=value`.
+#[macro_export]
+macro_rules! log_kv {
+    ($key:expr, $value:expr) => {
+        pinocchio::msg!("{}={}", $key, $value)
     };
 }
+
+/// Logs `label: <base58 address>`.
+#[macro_export]
+macro_rules! log_pubkey {
+    ($label:expr, $pubkey:expr) => {
+        pinocchio::msg!("{}: {}", $label, $crate::to_base58($pubkey))
+    };
+}
+
+/// Logs a lamport amount in SOL, as `label: 1.230000000 SOL`.
+#[macro_export]
+macro_rules! log_amount {
+    ($label:expr, $lamports:expr) => {
+        pinocchio::msg!("{}: {}", $label, $crate::LamportsAsSol($lamports))
+    };
+}
+
+/// Returns early with `NotEnoughAccountKeys` unless exactly `$count` accounts were provided.
+///
+/// ```ignore
+/// assert_accounts_count!(accounts, 5);
+/// ```
+#[macro_export]
+macro_rules! assert_accounts_count {
+    ($accounts:expr, $count:expr) => {
+        if $accounts.len().ne(&$count) {
+            pinocchio::msg!(
+                "Unexpected number of accounts (expected, actual): {:?}, {:?}",
+                $count,
+                $accounts.len()
+            );
+            return Err(pinocchio::program_error::ProgramError::NotEnoughAccountKeys.into());
+        }
+    };
+}
+
+/// Implements `InstructionDispatcher` for an instruction discriminator enum, routing each variant
+/// to its processor. Processors have the entrypoint signature `(program_id, accounts, data)` and
+/// receive the instruction data without the discriminator byte.
+///
+/// ```ignore
+/// instruction_set!(MyInstruction {
+///     Add => process_add,
+///     Initialize => process_initialize,
+/// });
+/// ```
+#[macro_export]
+macro_rules! instruction_set {
+    ($discriminator_name:ident { $($variant:ident => $processor:path),* $(,)? }) => {
+        impl $crate::InstructionDispatcher for $discriminator_name {
+            fn dispatch(
+                accounts: &[pinocchio::account_info::AccountInfo],
+                data: &[u8],
+                program_id: &pinocchio::pubkey::Pubkey,
+            ) -> pinocchio::ProgramResult {
+                let (tag, data) = data
+                    .split_first()
+                    .ok_or(pinocchio::program_error::ProgramError::InvalidInstructionData)?;
+                let ix = $discriminator_name::try_from(*tag).or(Err(
+                    pinocchio::program_error::ProgramError::InvalidInstructionData,
+                ))?;
+
+                match ix {
+                    $($discriminator_name::$variant => $processor(program_id, accounts, data),)*
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::InstructionDispatcher;
+    use borsh::BorshDeserialize;
+    use pinocchio::{
+        account_info::AccountInfo, program_error::ProgramError, pubkey::Pubkey, ProgramResult,
+    };
+
+    #[repr(u8)]
+    #[derive(Clone, Copy, Debug, Eq, PartialEq, num_enum::TryFromPrimitive)]
+    enum TestInstruction {
+        Echo = 0,
+    }
+
+    fn process_echo(program_id: &Pubkey, _accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+        if program_id.ne(&[1; 32]) || data.ne(&[9]) {
+            return Err(ProgramError::InvalidArgument);
+        }
+        Ok(())
+    }
+
+    instruction_set!(TestInstruction {
+        Echo => process_echo,
+    });
+
+    #[repr(u8)]
+    enum TestBorshInstruction {
+        Deposit = 0,
+    }
+
+    #[derive(borsh::BorshSerialize, borsh::BorshDeserialize)]
+    struct Deposit {
+        amount: u64,
+        memo: [u8; 4],
+    }
+
+    borsh_instruction!(TestBorshInstruction, Deposit, debug_fields = [amount]);
+
+    #[test]
+    fn instruction_set_passes_program_id_and_data() {
+        assert_eq!(TestInstruction::dispatch(&[], &[0, 9], &[1; 32]), Ok(()));
+        assert_eq!(
+            TestInstruction::dispatch(&[], &[0, 9], &[2; 32]),
+            Err(ProgramError::InvalidArgument)
+        );
+        assert_eq!(
+            TestInstruction::dispatch(&[], &[1], &[1; 32]),
+            Err(ProgramError::InvalidInstructionData)
+        );
+    }
+
+    #[test]
+    fn borsh_instruction_debug_prints_listed_fields() {
+        let deposit = Deposit {
+            amount: 5,
+            memo: [0; 4],
+        };
+        assert_eq!(format!("{deposit:?}"), "Deposit { amount: 5 }");
+        assert_eq!(deposit.to_bytes()[0], 0);
+    }
+
+    #[repr(u8)]
+    #[derive(num_enum::IntoPrimitive)]
+    enum TestAccountKind {
+        Vault = 0,
+    }
+
+    #[derive(borsh::BorshSerialize, borsh::BorshDeserialize)]
+    struct Vault {
+        balance: u64,
+        bump: u8,
+        authority: Pubkey,
+    }
+
+    account!(TestAccountKind, Vault, numeric_fields = [balance: u64, bump: u8]);
+
+    #[test]
+    fn numeric_field_asserts_check_bounds() {
+        let vault = Vault {
+            balance: 100,
+            bump: 254,
+            authority: [1; 32],
+        };
+        assert!(vault.assert_balance_gte(100).is_ok());
+        assert!(vault.assert_balance_lte(99).is_err());
+        assert!(vault.assert_bump_eq(254).is_ok());
+        assert_eq!(vault.authority, [1; 32]);
+    }
+
+    #[cfg(feature = "testing")]
+    #[test]
+    fn summary_lists_numeric_fields() {
+        let vault = Vault {
+            balance: 100,
+            bump: 254,
+            authority: [1; 32],
+        };
+        assert_eq!(vault.summary(), "balance: 100, bump: 254");
+    }
+}
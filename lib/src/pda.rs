@@ -0,0 +1,105 @@
+use pinocchio::{
+    instruction::Seed,
+    pubkey::{find_program_address, Pubkey},
+};
+
+/// A program derived address together with its canonical bump.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PdaInfo {
+    pub address: Pubkey,
+    pub bump: u8,
+}
+
+impl PdaInfo {
+    /// Derives the PDA of `seeds` for `program_id`, finding its canonical bump.
+    pub fn derive(seeds: &[&[u8]], program_id: &Pubkey) -> Self {
+        let (address, bump) = find_program_address(seeds, program_id);
+        Self { address, bump }
+    }
+
+    /// Returns `prefix_seeds` followed by the bump, i.e. the full seeds needed to sign for the PDA.
+    pub fn as_signer_seeds_with_bump(&self, prefix_seeds: &[&[u8]]) -> Vec<Vec<u8>> {
+        let mut seeds = Vec::with_capacity(prefix_seeds.len() + 1);
+        seeds.extend(prefix_seeds.iter().map(|seed| seed.to_vec()));
+        seeds.push(vec![self.bump]);
+        seeds
+    }
+}
+
+impl From<(Pubkey, u8)> for PdaInfo {
+    fn from((address, bump): (Pubkey, u8)) -> Self {
+        Self { address, bump }
+    }
+}
+
+/// Builds PDA seeds from typed components, owning the byte buffers so the seed slices stay valid.
+///
+/// ```ignore
+/// let seeds = SeedBuilder::new().prefix(b"vault").pubkey(owner.key()).u64(id);
+/// vault.assert_seeds_builder(&seeds, program_id)?;
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SeedBuilder {
+    seeds: Vec<Vec<u8>>,
+    bump: Option<[u8; 1]>,
+}
+
+impl SeedBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn prefix(mut self, prefix: &[u8]) -> Self {
+        self.seeds.push(prefix.to_vec());
+        self
+    }
+
+    pub fn pubkey(mut self, key: &Pubkey) -> Self {
+        self.seeds.push(key.to_vec());
+        self
+    }
+
+    /// Appends `n` as little-endian bytes.
+    pub fn u64(mut self, n: u64) -> Self {
+        self.seeds.push(n.to_le_bytes().to_vec());
+        self
+    }
+
+    pub fn u8(mut self, n: u8) -> Self {
+        self.seeds.push(vec![n]);
+        self
+    }
+
+    /// Sets the bump, which is always placed after the other seeds.
+    pub fn bump(mut self, bump: u8) -> Self {
+        self.bump = Some([bump]);
+        self
+    }
+
+    /// Returns the seeds, excluding the bump.
+    pub fn seed_slices(&self) -> Vec<&[u8]> {
+        self.seeds.iter().map(Vec::as_slice).collect()
+    }
+
+    pub fn bump_seed(&self) -> Option<u8> {
+        self.bump.map(|[bump]| bump)
+    }
+
+    /// Returns the seeds, followed by the bump if one was set.
+    pub fn build(self) -> Vec<Vec<u8>> {
+        let mut seeds = self.seeds;
+        seeds.extend(self.bump.map(|bump| bump.to_vec()));
+        seeds
+    }
+}
+
+impl<'a> From<&'a SeedBuilder> for Vec<Seed<'a>> {
+    fn from(builder: &'a SeedBuilder) -> Self {
+        builder
+            .seeds
+            .iter()
+            .map(|seed| Seed::from(seed.as_slice()))
+            .chain(builder.bump.iter().map(|bump| Seed::from(bump.as_slice())))
+            .collect()
+    }
+}
@@ -0,0 +1,66 @@
+//! Helpers for populating a `solana_program_test` environment with geppetto-format accounts.
+//!
+//! `BanksClient` cannot write accounts directly, so accounts are added either to the
+//! `ProgramTest` before it starts, or to a running `ProgramTestContext`.
+
+use borsh::BorshSerialize;
+use solana_program_test::{ProgramTest, ProgramTestContext};
+use solana_sdk::{account::Account, pubkey::Pubkey};
+
+use crate::Discriminator;
+
+/// Serializes `data` into the on-chain account layout: the discriminator byte followed by the
+/// borsh-encoded body.
+pub fn geppetto_account_data<T>(data: &T) -> Vec<u8>
+where
+    T: BorshSerialize + Discriminator,
+{
+    let mut bytes = vec![T::discriminator()];
+    bytes.extend(data.try_to_vec().expect("failed to serialize account"));
+    bytes
+}
+
+/// Adds a geppetto-format account to the test environment before it is started.
+pub fn add_geppetto_account<T>(
+    program_test: &mut ProgramTest,
+    address: Pubkey,
+    owner: Pubkey,
+    data: &T,
+    lamports: u64,
+) where
+    T: BorshSerialize + Discriminator,
+{
+    program_test.add_account(
+        address,
+        Account {
+            lamports,
+            data: geppetto_account_data(data),
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        },
+    );
+}
+
+/// Writes a geppetto-format account into a running test context.
+pub fn set_geppetto_account<T>(
+    context: &mut ProgramTestContext,
+    address: Pubkey,
+    owner: Pubkey,
+    data: &T,
+    lamports: u64,
+) where
+    T: BorshSerialize + Discriminator,
+{
+    context.set_account(
+        &address,
+        &Account {
+            lamports,
+            data: geppetto_account_data(data),
+            owner,
+            executable: false,
+            rent_epoch: 0,
+        }
+        .into(),
+    );
+}
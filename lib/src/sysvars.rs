@@ -0,0 +1,144 @@
+use pinocchio::{
+    account_info::AccountInfo, msg, program_error::ProgramError, pubkey::Pubkey,
+    sysvars::clock::Clock, ProgramResult,
+};
+
+use crate::AccountInfoValidation;
+
+/// Owner of all sysvar accounts.
+pub const SYSVAR_PROGRAM_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("Sysvar1111111111111111111111111111111111111");
+
+/// Address of the `Clock` sysvar.
+pub const CLOCK_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("SysvarC1ock11111111111111111111111111111111");
+
+/// Address of the `Rent` sysvar.
+pub const RENT_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("SysvarRent111111111111111111111111111111111");
+
+/// Address of the `EpochSchedule` sysvar.
+pub const EPOCH_SCHEDULE_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("SysvarEpochSchedu1e111111111111111111111111");
+
+/// Address of the `SlotHashes` sysvar.
+pub const SLOT_HASHES_ID: Pubkey =
+    pinocchio_pubkey::pubkey!("SysvarS1otHashes111111111111111111111111111");
+
+/// Asserts the current slot is strictly before `deadline_slot`.
+#[inline(always)]
+pub fn assert_clock_before(clock: &Clock, deadline_slot: u64) -> ProgramResult {
+    if clock.slot.ge(&deadline_slot) {
+        msg!(
+            "Clock is past deadline (deadline, current): {:?}, {:?}",
+            deadline_slot,
+            clock.slot
+        );
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Asserts the current slot is strictly after `start_slot`.
+#[inline(always)]
+pub fn assert_clock_after(clock: &Clock, start_slot: u64) -> ProgramResult {
+    if clock.slot.le(&start_slot) {
+        msg!(
+            "Clock is before start (start, current): {:?}, {:?}",
+            start_slot,
+            clock.slot
+        );
+        return Err(ProgramError::InvalidArgument);
+    }
+    Ok(())
+}
+
+/// Contents of the `EpochSchedule` sysvar, which pinocchio does not expose.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct EpochSchedule {
+    pub slots_per_epoch: u64,
+    pub leader_schedule_slot_offset: u64,
+    pub warmup: bool,
+    pub first_normal_epoch: u64,
+    pub first_normal_slot: u64,
+}
+
+impl EpochSchedule {
+    /// Size of the sysvar's serialized data.
+    pub const LEN: usize = 33;
+}
+
+/// Accessors for the `SlotHashes` sysvar, commonly used as a source of recent entropy.
+pub struct SlotHashesSysvar;
+
+impl SlotHashesSysvar {
+    /// Returns the hash of the most recent slot stored in the `SlotHashes` sysvar account.
+    pub fn get_recent_hash(account: &AccountInfo) -> Result<[u8; 32], ProgramError> {
+        account.assert_key(&SLOT_HASHES_ID)?;
+        let data = account.try_borrow_data()?;
+
+        // Layout: u64 entry count, followed by (u64 slot, [u8; 32] hash) entries, newest first.
+        let count = data
+            .get(..8)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+            .ok_or(ProgramError::InvalidAccountData)?;
+        if count.eq(&0) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mut hash = [0u8; 32];
+        hash.copy_from_slice(data.get(16..48).ok_or(ProgramError::InvalidAccountData)?);
+        Ok(hash)
+    }
+}
+
+/// Target duration of a slot on mainnet, in milliseconds.
+pub const DEFAULT_SLOT_DURATION_MS: u64 = 400;
+
+/// Estimates the mapping between slots and unix timestamps, anchored at the current `Clock`.
+///
+/// Slot durations vary in practice, so results are estimates and should not be relied on for
+/// exact deadlines.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SlotClock {
+    pub current_slot: u64,
+    pub current_unix_timestamp: i64,
+    pub slot_duration_ms: u64,
+}
+
+impl SlotClock {
+    /// Builds a `SlotClock` from the `Clock` sysvar, assuming the default slot duration.
+    pub fn from_clock(clock: &Clock) -> Self {
+        Self::from_clock_with_slot_duration(clock, DEFAULT_SLOT_DURATION_MS)
+    }
+
+    /// Builds a `SlotClock` from the `Clock` sysvar with a custom slot duration.
+    pub fn from_clock_with_slot_duration(clock: &Clock, slot_duration_ms: u64) -> Self {
+        Self {
+            current_slot: clock.slot,
+            current_unix_timestamp: clock.unix_timestamp,
+            slot_duration_ms,
+        }
+    }
+
+    /// Returns the estimated number of slots until `unix_timestamp`. Negative if it is in the past.
+    pub fn slots_until(&self, unix_timestamp: i64) -> i64 {
+        unix_timestamp
+            .saturating_sub(self.current_unix_timestamp)
+            .saturating_mul(1000)
+            / self.slot_duration_ms.max(1) as i64
+    }
+
+    /// Returns the estimated unix timestamp at which `slot` is reached.
+    pub fn unix_timestamp_at_slot(&self, slot: u64) -> i64 {
+        let slots = (slot as i64).saturating_sub(self.current_slot as i64);
+        self.current_unix_timestamp
+            .saturating_add(slots.saturating_mul(self.slot_duration_ms as i64) / 1000)
+    }
+}
+
+impl From<&Clock> for SlotClock {
+    fn from(clock: &Clock) -> Self {
+        Self::from_clock(clock)
+    }
+}
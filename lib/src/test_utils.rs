@@ -0,0 +1,59 @@
+//! Builds `AccountInfo`s over owned buffers for unit tests, using the runtime's input layout.
+
+use pinocchio::{account_info::AccountInfo, pubkey::Pubkey};
+
+/// Size of the account header the runtime places before the data: borrow state, signer,
+/// writable and executable flags, original data length, key, owner, lamports and data length.
+const ACCOUNT_HEADER_LEN: usize = 88;
+
+/// Spare room the runtime leaves after the data for in-place reallocs.
+const MAX_PERMITTED_DATA_INCREASE: usize = 10 * 1024;
+
+/// Owns the memory behind a test `AccountInfo`. Accounts start writable and not a signer.
+pub struct TestAccount {
+    buffer: Vec<u64>,
+}
+
+impl TestAccount {
+    pub fn new(key: Pubkey, owner: Pubkey, lamports: u64, data: &[u8]) -> Self {
+        let len = ACCOUNT_HEADER_LEN + data.len() + MAX_PERMITTED_DATA_INCREASE;
+        let mut account = Self {
+            buffer: vec![0; len.div_ceil(8)],
+        };
+        let bytes = account.bytes_mut();
+        bytes[2] = 1;
+        bytes[8..40].copy_from_slice(&key);
+        bytes[40..72].copy_from_slice(&owner);
+        bytes[72..80].copy_from_slice(&lamports.to_le_bytes());
+        bytes[80..88].copy_from_slice(&(data.len() as u64).to_le_bytes());
+        bytes[ACCOUNT_HEADER_LEN..ACCOUNT_HEADER_LEN + data.len()].copy_from_slice(data);
+        account
+    }
+
+    pub fn signer(mut self) -> Self {
+        self.bytes_mut()[1] = 1;
+        self
+    }
+
+    pub fn readonly(mut self) -> Self {
+        self.bytes_mut()[2] = 0;
+        self
+    }
+
+    pub fn executable(mut self) -> Self {
+        self.bytes_mut()[3] = 1;
+        self
+    }
+
+    /// Returns an `AccountInfo` over this account. Infos returned by repeated calls alias the same
+    /// account, like duplicate accounts in an instruction.
+    pub fn info(&mut self) -> AccountInfo {
+        // SAFETY: `AccountInfo` is a single pointer to the account header, and the buffer holds the
+        // header, data and realloc room in the runtime's layout, aligned to 8 bytes.
+        unsafe { core::mem::transmute::<*mut u64, AccountInfo>(self.buffer.as_mut_ptr()) }
+    }
+
+    fn bytes_mut(&mut self) -> &mut [u8] {
+        bytemuck::cast_slice_mut(&mut self.buffer)
+    }
+}
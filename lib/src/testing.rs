@@ -0,0 +1,14 @@
+//! Helpers for setting up account state in tests. Never use these in deployed programs.
+
+use pinocchio::account_info::AccountInfo;
+
+/// Overwrites the account's lamports, e.g. to set up balance preconditions in tests.
+///
+/// # Safety
+///
+/// This bypasses the lamports borrow tracking and the runtime's balance invariants. The caller
+/// must ensure no other reference to the account's lamports is alive, and must never use this
+/// in deployed programs.
+pub unsafe fn set_lamports_unchecked(info: &AccountInfo, lamports: u64) {
+    *info.borrow_mut_lamports_unchecked() = lamports;
+}
@@ -1,62 +1,92 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use bytemuck::Pod;
+use bytemuck::{AnyBitPattern, Pod};
 use pinocchio::{
-    account_info::AccountInfo, instruction::Seed, program_error::ProgramError, pubkey::Pubkey,
+    account_info::{AccountInfo, Ref, RefMut},
+    instruction::{AccountMeta, Instruction, Seed, Signer},
+    msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvars::{clock::Clock, rent::Rent},
+    ProgramResult,
 };
+use pinocchio_token::state::{Mint, TokenAccount};
 
-// pub trait AccountDeserialize {
-//     fn try_from_bytes(data: &[u8]) -> Result<&Self, ProgramError>;
-//     fn try_from_bytes_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError>;
-// }
-
-// impl<T> AccountDeserialize for T
-// where
-//     T: Discriminator + Pod,
-// {
-//     fn try_from_bytes(data: &[u8]) -> Result<&Self, ProgramError> {
-//         if Self::discriminator().ne(&data[0]) {
-//             return Err(ProgramError::InvalidAccountData);
-//         }
-//         bytemuck::try_from_bytes::<Self>(&data[8..]).or(Err(ProgramError::InvalidAccountData))
-//     }
-
-//     fn try_from_bytes_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError> {
-//         if Self::discriminator().ne(&data[0]) {
-//             return Err(ProgramError::InvalidAccountData);
-//         }
-//         bytemuck::try_from_bytes_mut::<Self>(&mut data[8..])
-//             .or(Err(ProgramError::InvalidAccountData))
-//     }
-// }
+use crate::{to_account_meta, try_pod_body, EpochSchedule, SeedBuilder};
+
+/// Zero-copy deserialization of `Pod` accounts, laid out as the discriminator byte, 7 bytes of
+/// padding, and the account body. Implemented by `pod_account!`.
+pub trait AccountDeserialize {
+    fn try_from_bytes(data: &[u8]) -> Result<&Self, ProgramError>;
+    fn try_from_bytes_mut(data: &mut [u8]) -> Result<&mut Self, ProgramError>;
+}
+
+/// Read-only counterpart to bytemuck account deserialization for types that are valid for any
+/// bit pattern but are not necessarily `Pod` (e.g. types with padding). Such types can be read
+/// from account data but never written back.
+pub trait AccountDeserializeAny {
+    fn try_from_bytes_any(data: &[u8]) -> Result<&Self, ProgramError>;
+}
+
+impl<T> AccountDeserializeAny for T
+where
+    T: Discriminator + AnyBitPattern,
+{
+    fn try_from_bytes_any(data: &[u8]) -> Result<&Self, ProgramError> {
+        if data.first().ne(&Some(&Self::discriminator())) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        try_pod_body::<Self>(data)
+    }
+}
 
 /// Account data is sometimes stored via a header and body type,
 /// where the former resolves the type of the latter (e.g. merkle trees with a generic size const).
 /// This trait parses a header type from the first N bytes of some data, and returns the remaining
 /// bytes, which are then available for further processing.
 ///
-/// See module-level tests for example usage.
-pub trait AccountHeaderDeserialize {
-    fn try_header_from_bytes(data: &[u8]) -> Result<(&Self, &[u8]), ProgramError>;
-    fn try_header_from_bytes_mut(data: &mut [u8]) -> Result<(&mut Self, &mut [u8]), ProgramError>;
-}
+/// Header types opt in with an empty impl, overriding [`Self::HEADER_OFFSET`] if the header is
+/// not stored at the `Pod` body offset:
+///
+/// ```ignore
+/// impl AccountHeaderDeserialize for MyHeader {}
+/// ```
+///
+/// This trait used to be implemented for every `Discriminator + Pod` type. Header types written
+/// against that blanket impl need the empty impl above for `try_header_from_bytes` to resolve.
+pub trait AccountHeaderDeserialize: Discriminator + Pod {
+    /// Byte offset of the header within the account data. Defaults to 8, the `Pod` body offset,
+    /// rather than 1 (directly after the discriminator): the header is cast in place, and an
+    /// offset of 1 would misalign any header with fields wider than a byte.
+    const HEADER_OFFSET: usize = 8;
 
-impl<T> AccountHeaderDeserialize for T
-where
-    T: Discriminator + Pod,
-{
+    /// Returns the header and the remaining bytes, failing with `InvalidAccountData` if the
+    /// discriminator does not match or the data is too short to hold the header.
     fn try_header_from_bytes(data: &[u8]) -> Result<(&Self, &[u8]), ProgramError> {
-        if Self::discriminator().ne(&data[0]) {
+        if data.first().ne(&Some(&Self::discriminator())) {
             return Err(ProgramError::InvalidAccountData);
         }
-        let (prefix, remainder) = data[8..].split_at(std::mem::size_of::<T>());
+        let (prefix, remainder) = data
+            .get(Self::HEADER_OFFSET..)
+            .filter(|rest| rest.len().ge(&std::mem::size_of::<Self>()))
+            .ok_or(ProgramError::InvalidAccountData)?
+            .split_at(std::mem::size_of::<Self>());
         Ok((
             bytemuck::try_from_bytes::<Self>(prefix).or(Err(ProgramError::InvalidAccountData))?,
             remainder,
         ))
     }
 
+    /// Mutable variant of [`Self::try_header_from_bytes`], performing the same discriminator and
+    /// length checks.
     fn try_header_from_bytes_mut(data: &mut [u8]) -> Result<(&mut Self, &mut [u8]), ProgramError> {
-        let (prefix, remainder) = data[8..].split_at_mut(std::mem::size_of::<T>());
+        if data.first().ne(&Some(&Self::discriminator())) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+        let (prefix, remainder) = data
+            .get_mut(Self::HEADER_OFFSET..)
+            .filter(|rest| rest.len().ge(&std::mem::size_of::<Self>()))
+            .ok_or(ProgramError::InvalidAccountData)?
+            .split_at_mut(std::mem::size_of::<Self>());
         Ok((
             bytemuck::try_from_bytes_mut::<Self>(prefix)
                 .or(Err(ProgramError::InvalidAccountData))?,
@@ -65,6 +95,12 @@ where
     }
 }
 
+/// Header whose fields encode the number of elements in the variable-length body that follows
+/// it. Used by [`crate::try_header_body_from_bytes`].
+pub trait HeaderWithCount {
+    fn body_count(&self) -> usize;
+}
+
 pub trait AccountValidation {
     fn assert<F>(&self, condition: F) -> Result<&Self, ProgramError>
     where
@@ -93,24 +129,368 @@ pub trait AccountValidation {
     fn assert_mut_msg<F>(&mut self, condition: F, msg: &str) -> Result<&mut Self, ProgramError>
     where
         F: Fn(&Self) -> bool;
+
+    /// Asserts every condition holds, failing on the first that does not. Closures of different
+    /// types can be combined by passing them as `&dyn Fn(&Self) -> bool`.
+    fn assert_all<F>(&self, conditions: &[F]) -> Result<&Self, ProgramError>
+    where
+        F: Fn(&Self) -> bool,
+    {
+        if let Some(index) = conditions.iter().position(|condition| !condition(self)) {
+            msg!("Account condition {:?} failed", index);
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(self)
+    }
+
+    /// Asserts at least one condition holds.
+    fn assert_any<F>(&self, conditions: &[F]) -> Result<&Self, ProgramError>
+    where
+        F: Fn(&Self) -> bool,
+    {
+        if !conditions.iter().any(|condition| condition(self)) {
+            msg!("No account condition holds");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(self)
+    }
+
+    /// Asserts `signer` signed the transaction and is the authority stored in the field selected
+    /// by `field`.
+    fn assert_authority<F>(&self, field: F, signer: &AccountInfo) -> Result<&Self, ProgramError>
+    where
+        F: Fn(&Self) -> &Pubkey,
+    {
+        self.assert_authority_err(field, signer, ProgramError::InvalidAccountData)
+    }
+
+    /// Same as `assert_authority`, returning `err` if `signer` is not the stored authority.
+    fn assert_authority_err<F>(
+        &self,
+        field: F,
+        signer: &AccountInfo,
+        err: ProgramError,
+    ) -> Result<&Self, ProgramError>
+    where
+        F: Fn(&Self) -> &Pubkey,
+    {
+        if !signer.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        if field(self).ne(signer.key()) {
+            return Err(err);
+        }
+        Ok(self)
+    }
+
+    /// Same as `assert_authority`, logging `msg` if `signer` is not the stored authority.
+    fn assert_authority_msg<F>(
+        &self,
+        field: F,
+        signer: &AccountInfo,
+        msg: &str,
+    ) -> Result<&Self, ProgramError>
+    where
+        F: Fn(&Self) -> &Pubkey,
+    {
+        if !signer.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+        crate::assert(
+            field(self).eq(signer.key()),
+            ProgramError::InvalidAccountData,
+            msg,
+        )?;
+        Ok(self)
+    }
+
+    /// Post-condition guard asserting the field selected by `field` still matches its value in
+    /// `snapshot`, e.g. to detect tampering across a CPI.
+    fn assert_fields_unchanged<V, F>(
+        &self,
+        snapshot: &Self,
+        field: F,
+    ) -> Result<&Self, ProgramError>
+    where
+        V: PartialEq + ?Sized,
+        F: Fn(&Self) -> &V,
+    {
+        if field(self).ne(field(snapshot)) {
+            msg!("Account field changed unexpectedly");
+            return Err(ProgramError::InvalidAccountData);
+        }
+        Ok(self)
+    }
 }
 
 pub trait AccountInfoValidation {
     fn assert_signer(&self) -> Result<&Self, ProgramError>;
+    /// Asserts the account did not sign the transaction, failing with `InvalidAccountData`.
+    fn assert_not_signer(&self) -> Result<&Self, ProgramError>;
     fn assert_writable(&self) -> Result<&Self, ProgramError>;
+    /// Asserts the account is writable only when `condition` holds, e.g. for accounts that are
+    /// modified by some code paths of an instruction but not others.
+    fn assert_writable_if(&self, condition: bool) -> Result<&Self, ProgramError>;
+    fn assert_not_writable(&self) -> Result<&Self, ProgramError>;
+    /// Asserts the account is writable if `should_be_writable`, and read-only otherwise.
+    fn assert_writable_matches(&self, should_be_writable: bool) -> Result<&Self, ProgramError>;
     fn assert_executable(&self) -> Result<&Self, ProgramError>;
+    /// Succeeds when the account has no data, failing with `AccountAlreadyInitialized` otherwise.
     fn assert_empty(&self) -> Result<&Self, ProgramError>;
+    /// Succeeds when the account has a nonzero data length, failing with `UninitializedAccount`
+    /// otherwise.
     fn assert_not_empty(&self) -> Result<&Self, ProgramError>;
-    fn assert_type<T: Discriminator>(&self, program_id: &Pubkey) -> Result<&Self, ProgramError>;
+    fn assert_type<T: AccountDiscriminator>(
+        &self,
+        program_id: &Pubkey,
+    ) -> Result<&Self, ProgramError>;
+    /// Checks only the discriminator, without asserting the account owner. Useful for cheaply
+    /// routing accounts by type before the full `assert_type` check.
+    fn assert_discriminator<T: AccountDiscriminator>(&self) -> Result<&Self, ProgramError>;
+    /// Checks the version byte stored immediately after the discriminator.
+    fn assert_version(&self, expected: u8) -> Result<&Self, ProgramError>;
+    /// Checks the account data can hold a `Pod` account of type `T`, i.e. the padded discriminator
+    /// followed by the body.
+    fn assert_min_size<T: Discriminator>(&self) -> Result<&Self, ProgramError>;
+    /// Checks the account data is exactly `expected` bytes long.
+    fn assert_data_len(&self, expected: usize) -> Result<&Self, ProgramError>;
+    /// Checks the account data is at least `min` bytes long.
+    fn assert_data_len_gte(&self, min: usize) -> Result<&Self, ProgramError>;
+    /// Checks the account holds at least `min` lamports.
+    fn assert_lamports_gte(&self, min: u64) -> Result<&Self, ProgramError>;
+    /// Checks the account holds strictly fewer than `max` lamports.
+    fn assert_lamports_lt(&self, max: u64) -> Result<&Self, ProgramError>;
+    /// Checks the account holds enough lamports to be rent exempt for its current data length.
+    fn assert_rent_exempt(&self, rent: &Rent) -> Result<&Self, ProgramError>;
+    /// Checks the account holds enough lamports to be rent exempt for `data_len` bytes, reading
+    /// rent from the provided sysvar account. Returns `AccountNotRentExempt` otherwise.
+    fn assert_rent_exempt_for(
+        &self,
+        data_len: usize,
+        rent_sysvar: &AccountInfo,
+    ) -> Result<&Self, ProgramError>;
     fn assert_program(&self, program_id: &Pubkey) -> Result<&Self, ProgramError>;
-    // fn is_sysvar(&self, sysvar_id: &Pubkey) -> Result<&Self, ProgramError>;
+    /// Checks the account is the system program.
+    fn assert_system_program(&self) -> Result<&Self, ProgramError>;
+    /// Checks the account is the SPL Token program.
+    fn assert_token_program(&self) -> Result<&Self, ProgramError>;
+    /// Checks the account is the associated token account program.
+    fn assert_associated_token_program(&self) -> Result<&Self, ProgramError>;
+    /// Checks the account is the Token-2022 program.
+    fn assert_token_program_2022(&self) -> Result<&Self, ProgramError>;
+    /// Checks the account is either the SPL Token or the Token-2022 program.
+    fn assert_token_program_any(&self) -> Result<&Self, ProgramError>;
+    /// Checks the account is the sysvar at `sysvar_id`, returning `InvalidArgument` otherwise.
+    fn assert_sysvar(&self, sysvar_id: &Pubkey) -> Result<&Self, ProgramError>;
     fn assert_key(&self, address: &Pubkey) -> Result<&Self, ProgramError>;
+    fn assert_key_not(&self, forbidden: &Pubkey) -> Result<&Self, ProgramError>;
+    fn assert_on_curve(&self) -> Result<&Self, ProgramError>;
+    fn assert_not_on_curve(&self) -> Result<&Self, ProgramError>;
     fn assert_owner(&self, program_id: &Pubkey) -> Result<&Self, ProgramError>;
+    fn assert_not_owner(&self, program_id: &Pubkey) -> Result<&Self, ProgramError>;
+    /// Checks the account is owned by one of `owners`, e.g. either token program.
+    fn assert_owned_by_any(&self, owners: &[&Pubkey]) -> Result<&Self, ProgramError>;
     fn assert_seeds(&self, seeds: &[&[u8]], program_id: &Pubkey) -> Result<&Self, ProgramError>;
+    /// Same as `assert_seeds`, but returns the canonical bump for subsequent signed CPIs.
+    fn assert_seeds_canonical(
+        &self,
+        seeds: &[&[u8]],
+        program_id: &Pubkey,
+    ) -> Result<u8, ProgramError>;
+    fn assert_seeds_with_provided_bump(
+        &self,
+        seeds: &[&[u8]],
+        bump: u8,
+        program_id: &Pubkey,
+    ) -> Result<&Self, ProgramError>;
+    /// Validates the account is the PDA of the builder's seeds, using its bump if one was set and
+    /// the canonical bump otherwise.
+    fn assert_seeds_builder(
+        &self,
+        seeds: &SeedBuilder,
+        program_id: &Pubkey,
+    ) -> Result<&Self, ProgramError>;
+    /// Validates the account is the PDA of `prefix_seeds` using the bump stored in its own `T`
+    /// data, as extracted by `get_bump`.
+    fn assert_seeds_pda_match_stored_bump<T, F>(
+        &self,
+        prefix_seeds: &[&[u8]],
+        program_id: &Pubkey,
+        get_bump: F,
+    ) -> Result<&Self, ProgramError>
+    where
+        T: Discriminator + BorshDeserialize,
+        F: Fn(&T) -> u8;
+
+    fn assert_signer_err<E: Into<ProgramError>>(&self, err: E) -> Result<&Self, ProgramError>;
+    fn assert_writable_err<E: Into<ProgramError>>(&self, err: E) -> Result<&Self, ProgramError>;
+    fn assert_executable_err<E: Into<ProgramError>>(&self, err: E) -> Result<&Self, ProgramError>;
+    fn assert_empty_err<E: Into<ProgramError>>(&self, err: E) -> Result<&Self, ProgramError>;
+    fn assert_not_empty_err<E: Into<ProgramError>>(&self, err: E) -> Result<&Self, ProgramError>;
+    fn assert_type_err<T: AccountDiscriminator, E: Into<ProgramError>>(
+        &self,
+        program_id: &Pubkey,
+        err: E,
+    ) -> Result<&Self, ProgramError>;
+    fn assert_program_err<E: Into<ProgramError>>(
+        &self,
+        program_id: &Pubkey,
+        err: E,
+    ) -> Result<&Self, ProgramError>;
+    fn assert_key_err<E: Into<ProgramError>>(
+        &self,
+        address: &Pubkey,
+        err: E,
+    ) -> Result<&Self, ProgramError>;
+    fn assert_owner_err<E: Into<ProgramError>>(
+        &self,
+        program_id: &Pubkey,
+        err: E,
+    ) -> Result<&Self, ProgramError>;
+    fn assert_seeds_err<E: Into<ProgramError>>(
+        &self,
+        seeds: &[&[u8]],
+        program_id: &Pubkey,
+        err: E,
+    ) -> Result<&Self, ProgramError>;
+}
+
+/// Maximum borsh-serialized size of a type, used to size account allocations.
+///
+/// Derive with `#[derive(BorshSize)]`; `Vec` and `String` fields require `#[max_len(N)]`.
+pub trait BorshSize {
+    const MAX_SIZE: usize;
+}
+
+macro_rules! impl_borsh_size {
+    ($($ty:ty),*) => {
+        $(
+            impl BorshSize for $ty {
+                const MAX_SIZE: usize = std::mem::size_of::<$ty>();
+            }
+        )*
+    };
+}
+
+impl_borsh_size!(
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    f32,
+    f64,
+    bool,
+    ()
+);
+
+impl<T: BorshSize, const N: usize> BorshSize for [T; N] {
+    const MAX_SIZE: usize = N * T::MAX_SIZE;
+}
+
+impl<T: BorshSize> BorshSize for Option<T> {
+    const MAX_SIZE: usize = 1 + T::MAX_SIZE;
 }
 
 pub trait Discriminator {
     fn discriminator() -> u8;
+
+    /// Returns the discriminator as a byte array, e.g. for use as a PDA seed.
+    fn discriminator_as_arr() -> [u8; 1] {
+        [Self::discriminator()]
+    }
+}
+
+/// 2-byte discriminator for programs with more than 256 account types. Implemented by
+/// [`crate::account16!`].
+pub trait Discriminator16 {
+    fn discriminator() -> u16;
+}
+
+/// 4-byte discriminator. Implemented by [`crate::account32!`].
+pub trait Discriminator32 {
+    fn discriminator() -> u32;
+}
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for u8 {}
+    impl Sealed for u16 {}
+    impl Sealed for u32 {}
+}
+
+/// Integer type of a discriminator, stored little-endian at the start of the account data.
+/// Implemented for `u8`, `u16` and `u32`.
+pub trait DiscriminatorWidth: sealed::Sealed + Copy + Eq + core::fmt::Debug {
+    /// Number of bytes the discriminator occupies.
+    const LEN: usize;
+
+    /// Reads the discriminator from the start of `data`, if it is long enough.
+    fn read(data: &[u8]) -> Option<Self>;
+
+    /// Writes the discriminator to the start of `data`. Panics if `data` is too short.
+    fn write(self, data: &mut [u8]);
+}
+
+macro_rules! impl_discriminator_width {
+    ($($width:ty),*) => {
+        $(
+            impl DiscriminatorWidth for $width {
+                const LEN: usize = std::mem::size_of::<$width>();
+
+                fn read(data: &[u8]) -> Option<Self> {
+                    data.get(..Self::LEN)
+                        .map(|bytes| Self::from_le_bytes(bytes.try_into().unwrap()))
+                }
+
+                fn write(self, data: &mut [u8]) {
+                    data[..Self::LEN].copy_from_slice(&self.to_le_bytes());
+                }
+            }
+        )*
+    };
+}
+
+impl_discriminator_width!(u8, u16, u32);
+
+/// Discriminator of any width, accepted by the loaders that support `Discriminator16` and
+/// `Discriminator32` accounts. Implemented for every `Discriminator`, and by `account16!` and
+/// `account32!`.
+pub trait AccountDiscriminator {
+    type Width: DiscriminatorWidth;
+
+    fn account_discriminator() -> Self::Width;
+
+    /// Returns whether `data` starts with this type's discriminator.
+    fn discriminator_matches(data: &[u8]) -> bool {
+        Self::Width::read(data).eq(&Some(Self::account_discriminator()))
+    }
+}
+
+impl<T: Discriminator> AccountDiscriminator for T {
+    type Width = u8;
+
+    fn account_discriminator() -> u8 {
+        T::discriminator()
+    }
+}
+
+/// Layout version of an account, stored in the byte immediately after the discriminator.
+/// Implemented by `versioned_account!`.
+pub trait AccountVersion {
+    fn version() -> u8;
+}
+
+/// 8-byte discriminator used by Anchor programs, computed as the first 8 bytes of
+/// `sha256("account:<TypeName>")`. Implemented by [`crate::anchor_account!`].
+pub trait AnchorDiscriminator {
+    fn discriminator() -> [u8; 8];
 }
 
 /// Performs:
@@ -118,14 +498,44 @@ pub trait Discriminator {
 /// 2. Discriminator byte check
 /// 3. Checked bytemuck conversion of account data to &T or &mut T.
 pub trait AsAccount {
+    /// Deserializes a borsh account owned by `program_id`. Trailing space after the body, e.g.
+    /// from allocating `BorshSize::MAX_SIZE`, is ignored.
     fn as_account<T>(&self, program_id: &Pubkey) -> Result<T, ProgramError>
     where
-        T: BorshDeserialize + BorshSerialize + Discriminator;
+        T: BorshDeserialize + BorshSerialize + AccountDiscriminator;
+
+    /// Same as `as_account`, but returns `Ok(None)` if the account has no data.
+    fn as_account_option<T>(&self, program_id: &Pubkey) -> Result<Option<T>, ProgramError>
+    where
+        T: BorshDeserialize + BorshSerialize + AccountDiscriminator;
+
+    /// Same as `as_account`, but for accounts created by Anchor programs, which are prefixed with
+    /// an 8-byte discriminator instead of a single byte.
+    fn as_anchor_account<T>(&self, program_id: &Pubkey) -> Result<T, ProgramError>
+    where
+        T: BorshDeserialize + AnchorDiscriminator;
 
+    /// Writes a borsh account, growing the account data if the serialized value no longer fits.
+    /// The account is never shrunk; leftover trailing space is zeroed.
     fn save_account<T>(&self, program_id: &Pubkey, account: &T) -> Result<(), ProgramError>
     where
-        T: BorshDeserialize + BorshSerialize + Discriminator;
+        T: BorshDeserialize + BorshSerialize + AccountDiscriminator;
 
+    /// Same as `save_account`, but skips the write when the serialized data matches the bytes
+    /// already stored in the account. Returns whether a write occurred.
+    fn save_account_if_changed<T>(
+        &self,
+        program_id: &Pubkey,
+        account: &T,
+    ) -> Result<bool, ProgramError>
+    where
+        T: BorshDeserialize + BorshSerialize + AccountDiscriminator;
+
+    /// Allocates and initializes a new account assigned to `owner`, sized by
+    /// [`crate::account_size!`] so it can hold any value of `T`. The account address must be a
+    /// PDA of `owner`, and since only the owner may write account data, `owner` must be the
+    /// invoking program. To allocate an account for a third-party program, use
+    /// [`crate::allocate_account_with_owner`] and let that program initialize the data.
     fn create_account<T>(
         &self,
         data: &T,
@@ -135,32 +545,223 @@ pub trait AsAccount {
         seeds: &[Seed],
     ) -> Result<(), ProgramError>
     where
-        T: BorshDeserialize + BorshSerialize + Discriminator;
+        T: BorshDeserialize + BorshSerialize + BorshSize + AccountDiscriminator;
+
+    /// Deserializes a versioned account, laid out as the discriminator byte, the version byte,
+    /// and the borsh-encoded body. Fails if the stored version does not match `T::version()`.
+    fn as_versioned_account<T>(&self, program_id: &Pubkey) -> Result<T, ProgramError>
+    where
+        T: BorshDeserialize + Discriminator + AccountVersion;
+
+    /// Writes a versioned account, growing the account data as needed.
+    fn save_versioned_account<T>(&self, program_id: &Pubkey, data: &T) -> Result<(), ProgramError>
+    where
+        T: BorshSerialize + Discriminator + AccountVersion;
 
-    // fn as_account_mut<T>(&self, program_id: &Pubkey) -> Result<&mut T, ProgramError>
-    // where
-    //     T: BorshDeserialize + BorshSerialize + Discriminator;
+    /// Reads the account in its `Old` layout, converts it with `migrate`, and writes it back in
+    /// its `New` layout, growing the account data as needed.
+    fn migrate_account<Old, New, F>(
+        &self,
+        program_id: &Pubkey,
+        migrate: F,
+    ) -> Result<New, ProgramError>
+    where
+        Old: BorshDeserialize + Discriminator + AccountVersion,
+        New: BorshSerialize + Discriminator + AccountVersion,
+        F: FnOnce(Old) -> New;
+
+    /// Mutably borrows a writable `Pod` account owned by `program_id` in place, without a
+    /// deserialize-modify-reserialize cycle. The account data stays borrowed until the returned
+    /// guard is dropped.
+    fn as_account_mut<T>(&self, program_id: &Pubkey) -> Result<RefMut<'_, T>, ProgramError>
+    where
+        T: Pod + Discriminator;
 }
 
-#[cfg(feature = "spl")]
+/// Reads SPL Token program accounts in place, after checking they are owned by the token program
+/// and sized for the expected state.
 pub trait AsSplToken {
-    fn as_mint(&self) -> Result<spl_token::state::Mint, ProgramError>;
-    fn as_token_account(&self) -> Result<spl_token::state::Account, ProgramError>;
+    fn as_mint(&self) -> Result<Ref<'_, Mint>, ProgramError>;
+    fn as_token_account(&self) -> Result<Ref<'_, TokenAccount>, ProgramError>;
     fn as_associated_token_account(
         &self,
         owner: &Pubkey,
         mint: &Pubkey,
-    ) -> Result<spl_token::state::Account, ProgramError>;
+    ) -> Result<Ref<'_, TokenAccount>, ProgramError>;
+    fn total_supply(&self) -> Result<u64, ProgramError>;
+    fn decimals(&self) -> Result<u8, ProgramError>;
+    fn is_initialized(&self) -> Result<bool, ProgramError>;
+
+    /// Asserts the account is the native mint (wrapped SOL).
+    ///
+    /// The native mint differs from regular mints: it is always initialized and has no mint
+    /// authority, its `decimals` mirror SOL (9) rather than being chosen by an issuer, and its
+    /// `supply` is not tracked since wrapped balances are backed directly by lamports held in
+    /// token accounts (see `sync_native`).
+    fn assert_is_native_mint(&self) -> Result<&Self, ProgramError>;
+}
+
+/// Token program CPIs invoked on a token account, e.g. `token_account.mint_to(...)`. Each returns
+/// the token account so calls can be chained with validation. The `_signed` variants sign for PDA
+/// authorities.
+pub trait SplTokenCpi {
+    fn mint_to(
+        &self,
+        mint: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+        decimals: u8,
+    ) -> Result<&Self, ProgramError>;
+    fn mint_to_signed(
+        &self,
+        mint: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+        decimals: u8,
+        signers: &[Signer],
+    ) -> Result<&Self, ProgramError>;
+    fn burn(
+        &self,
+        mint: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+    ) -> Result<&Self, ProgramError>;
+    fn burn_signed(
+        &self,
+        mint: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+        signers: &[Signer],
+    ) -> Result<&Self, ProgramError>;
+    /// Transfers with `TransferChecked`, which validates the mint and its decimals.
+    fn spl_transfer(
+        &self,
+        mint: &AccountInfo,
+        destination: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+        decimals: u8,
+    ) -> Result<&Self, ProgramError>;
+    fn spl_transfer_signed(
+        &self,
+        mint: &AccountInfo,
+        destination: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+        decimals: u8,
+        signers: &[Signer],
+    ) -> Result<&Self, ProgramError>;
+    fn approve(
+        &self,
+        delegate: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+    ) -> Result<&Self, ProgramError>;
+    fn approve_signed(
+        &self,
+        delegate: &AccountInfo,
+        authority: &AccountInfo,
+        amount: u64,
+        signers: &[Signer],
+    ) -> Result<&Self, ProgramError>;
+}
+
+/// Token-2022 (Token Extensions) counterpart of `AsSplToken`. Owner checks use the Token-2022
+/// program ID, and accounts with extensions are parsed by their base state.
+///
+/// `AsSplToken` is always available, so programs that interact with both token programs only
+/// need the `spl2022` feature.
+#[cfg(feature = "spl2022")]
+pub trait AsSplToken2022 {
+    fn as_mint_2022(&self) -> Result<spl_token_2022::state::Mint, ProgramError>;
+    fn as_token_account_2022(&self) -> Result<spl_token_2022::state::Account, ProgramError>;
+    fn as_associated_token_account_2022(
+        &self,
+        owner: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<spl_token_2022::state::Account, ProgramError>;
 }
 
-// TODO Work in progress
+/// Fungibility checks on a mint, based on `classify_mint`.
+pub trait MintValidation {
+    fn assert_fungible(&self) -> Result<&Self, ProgramError>;
+    fn assert_nonfungible(&self) -> Result<&Self, ProgramError>;
+}
+
+/// Supply cap checks for programs with capped token issuance.
+pub trait MintCapValidation {
+    /// Checks minting `amount_to_mint` more tokens keeps the supply at or below `max_supply`.
+    fn assert_max_supply(
+        &self,
+        amount_to_mint: u64,
+        max_supply: u64,
+    ) -> Result<&Self, ProgramError>;
+}
+
+/// Parses sysvar accounts passed to the instruction, after checking their address. Returns
+/// `InvalidArgument` if the account is not the expected sysvar.
+pub trait SysvarAccess {
+    fn as_clock(&self) -> Result<Clock, ProgramError>;
+    fn as_rent(&self) -> Result<Rent, ProgramError>;
+    fn as_epoch_schedule(&self) -> Result<EpochSchedule, ProgramError>;
+}
+
+/// Lamport movement between accounts.
+///
+/// `send_*` debit the account's lamports directly, which only works when it is owned by the
+/// invoking program, and do not show up as transfers in explorers. Use `collect` to pull lamports
+/// from a system-owned account (e.g. a signing payer) via a system program transfer.
 pub trait LamportTransfer<'a> {
-    fn send(&'a self, lamports: u64, to: &'a AccountInfo) -> Result<(), ProgramError>;
+    /// Moves lamports directly to `to`, returning `InsufficientFunds` if the balance is too low
+    /// and `ArithmeticOverflow` if the recipient's balance would overflow.
+    fn send_safe(&'a self, lamports: u64, to: &'a AccountInfo) -> Result<(), ProgramError>;
+
+    /// Moves lamports directly to `to` without checking the balance. Panics on underflow.
+    fn send_unchecked(&'a self, lamports: u64, to: &'a AccountInfo) -> Result<(), ProgramError>;
+
+    #[deprecated(note = "use `send_safe` or `send_unchecked` instead")]
+    fn send(&'a self, lamports: u64, to: &'a AccountInfo) -> Result<(), ProgramError> {
+        self.send_safe(lamports, to)
+    }
+
+    /// Moves the account's entire balance to `to`.
+    fn send_all(&'a self, to: &'a AccountInfo) -> Result<(), ProgramError>;
+
     fn collect(&'a self, lamports: u64, from: &'a AccountInfo) -> Result<(), ProgramError>;
 }
 
 pub trait CloseAccount<'a> {
     fn close(&'a self, to: &'a AccountInfo) -> Result<(), ProgramError>;
+
+    /// Same as `close`, but explicitly zeroes the discriminator byte before reallocating, so the
+    /// address reads as uninitialized even if stale data is observed later.
+    fn close_with_discriminator_zero(&'a self, to: &'a AccountInfo) -> Result<(), ProgramError>;
+
+    /// Closes the account into a wrapped SOL token account, then syncs the vault so the
+    /// reclaimed lamports are reflected in its token balance.
+    ///
+    /// Only native mint (wrapped SOL) vaults can absorb lamports this way. Lamports sent to a
+    /// token account of any other mint are not withdrawable by its owner, so for those vaults
+    /// close into a system account instead and move the funds in a separate step.
+    fn close_to_token_account(
+        &'a self,
+        vault: &'a AccountInfo,
+        token_program: &'a AccountInfo,
+    ) -> Result<(), ProgramError>;
+
+    /// Closes the account into the upgrade authority of `program_id`, as recorded in the
+    /// program's `ProgramData` account. Fails if the program is immutable.
+    fn close_into_program_authority(
+        &'a self,
+        program_data: &'a AccountInfo,
+        authority: &'a AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<(), ProgramError>;
+}
+
+/// JSON description of an event's fields, implemented by the `#[schema]` attribute.
+pub trait EventSchema {
+    const SCHEMA: &'static str;
 }
 
 pub trait Loggable {
@@ -168,6 +769,229 @@ pub trait Loggable {
     fn log_return(&self);
 }
 
+impl Loggable for Vec<u8> {
+    fn log(&self) {
+        pinocchio::log::sol_log_data(&[self.as_slice()]);
+    }
+
+    fn log_return(&self) {
+        pinocchio::program::set_return_data(self.as_slice());
+    }
+}
+
+impl Loggable for &[u8] {
+    fn log(&self) {
+        pinocchio::log::sol_log_data(&[self]);
+    }
+
+    fn log_return(&self) {
+        pinocchio::program::set_return_data(self);
+    }
+}
+
+/// Bytes an event is logged as, used to batch several events into one log with
+/// [`crate::log_many`]. Implemented by `event!`.
+pub trait ToLogBytes {
+    fn to_log_bytes(&self) -> Vec<u8>;
+}
+
+impl ToLogBytes for Vec<u8> {
+    fn to_log_bytes(&self) -> Vec<u8> {
+        self.clone()
+    }
+}
+
+impl ToLogBytes for &[u8] {
+    fn to_log_bytes(&self) -> Vec<u8> {
+        self.to_vec()
+    }
+}
+
+/// Single entry point routing instruction data to its processor, implemented by
+/// `instruction_set!`.
+pub trait InstructionDispatcher {
+    fn dispatch(accounts: &[AccountInfo], data: &[u8], program_id: &Pubkey) -> ProgramResult;
+}
+
+/// Instruction data serialized as its discriminator followed by its borsh-encoded body,
+/// implemented by `borsh_instruction!`.
+pub trait BorshInstructionData {
+    fn to_instruction_data(&self) -> Vec<u8>;
+}
+
+/// Typed CPI interface a program exposes so other programs can invoke it.
+///
+/// Accounts are passed as a fixed-size array, mirroring `pinocchio::program::invoke_signed`,
+/// and their signer and writable flags are forwarded as-is.
+pub trait GeppettoCpi {
+    type Instruction: BorshInstructionData;
+
+    fn invoke_cpi<const N: usize>(
+        program: &AccountInfo,
+        accounts: &[&AccountInfo; N],
+        instruction: &Self::Instruction,
+    ) -> ProgramResult {
+        Self::invoke_cpi_signed(program, accounts, instruction, &[])
+    }
+
+    fn invoke_cpi_signed<const N: usize>(
+        program: &AccountInfo,
+        accounts: &[&AccountInfo; N],
+        instruction: &Self::Instruction,
+        signer_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        let (metas, data) = cpi_metas_and_data(accounts, instruction);
+
+        let seeds = signer_seeds
+            .iter()
+            .map(|seeds| seeds.iter().map(|s| Seed::from(*s)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let signers = seeds
+            .iter()
+            .map(|seeds| Signer::from(seeds.as_slice()))
+            .collect::<Vec<_>>();
+
+        pinocchio::program::invoke_signed(
+            &Instruction {
+                program_id: program.key(),
+                accounts: &metas,
+                data: &data,
+            },
+            accounts,
+            &signers,
+        )
+    }
+}
+
+/// Account metas and instruction data for a [`GeppettoCpi`] invocation.
+fn cpi_metas_and_data<'a, I: BorshInstructionData, const N: usize>(
+    accounts: &[&'a AccountInfo; N],
+    instruction: &I,
+) -> (Vec<AccountMeta<'a>>, Vec<u8>) {
+    let metas = accounts.iter().copied().map(to_account_meta).collect();
+    (metas, instruction.to_instruction_data())
+}
+
 pub trait ProgramOwner {
     fn owner() -> Pubkey;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestAccount;
+    use bytemuck::Zeroable;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Pod, Zeroable)]
+    struct Header {
+        depth: u64,
+    }
+
+    impl Discriminator for Header {
+        fn discriminator() -> u8 {
+            3
+        }
+    }
+
+    impl AccountHeaderDeserialize for Header {}
+
+    #[test]
+    fn header_from_bytes_reads_at_offset() {
+        let mut words = [0u64; 3];
+        words[1] = 7;
+        let data = bytemuck::bytes_of_mut(&mut words);
+        data[0] = Header::discriminator();
+
+        let (header, remainder) = Header::try_header_from_bytes(data).unwrap();
+        assert_eq!(header.depth, 7);
+        assert_eq!(remainder.len(), 8);
+
+        let (header, _) = Header::try_header_from_bytes_mut(data).unwrap();
+        header.depth = 9;
+        assert_eq!(words[1], 9);
+    }
+
+    #[test]
+    fn header_from_bytes_rejects_wrong_discriminator() {
+        let mut words = [0u64; 2];
+        let data = bytemuck::bytes_of_mut(&mut words);
+        data[0] = Header::discriminator() + 1;
+
+        assert_eq!(
+            Header::try_header_from_bytes(data).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            Header::try_header_from_bytes_mut(data).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    #[test]
+    fn header_from_bytes_rejects_short_data() {
+        assert_eq!(
+            Header::try_header_from_bytes(&[]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            Header::try_header_from_bytes_mut(&mut []).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+
+        let mut words = [0u64; 2];
+        let data = bytemuck::bytes_of_mut(&mut words);
+        data[0] = Header::discriminator();
+        assert_eq!(
+            Header::try_header_from_bytes(&data[..12]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            Header::try_header_from_bytes_mut(&mut data[..12]).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    struct Increment {
+        amount: u8,
+    }
+
+    impl BorshInstructionData for Increment {
+        fn to_instruction_data(&self) -> Vec<u8> {
+            vec![1, self.amount]
+        }
+    }
+
+    struct CounterProgram;
+
+    impl GeppettoCpi for CounterProgram {
+        type Instruction = Increment;
+    }
+
+    #[test]
+    fn geppetto_cpi_forwards_accounts_and_instruction_data() {
+        let mut program = TestAccount::new([9; 32], [0; 32], 1, &[])
+            .readonly()
+            .executable();
+        let mut counter = TestAccount::new([1; 32], [9; 32], 0, &[0; 8]);
+        let mut authority = TestAccount::new([2; 32], [0; 32], 0, &[])
+            .readonly()
+            .signer();
+        let (program, counter, authority) = (program.info(), counter.info(), authority.info());
+        let accounts = [&counter, &authority];
+        let instruction = Increment { amount: 3 };
+
+        let (metas, data) = cpi_metas_and_data(&accounts, &instruction);
+
+        assert_eq!(data, [1, 3]);
+        let flags = metas
+            .iter()
+            .map(|meta| (*meta.pubkey, meta.is_writable, meta.is_signer))
+            .collect::<Vec<_>>();
+        assert_eq!(flags, [([1; 32], true, false), ([2; 32], false, true)]);
+        assert_eq!(
+            CounterProgram::invoke_cpi(&program, &accounts, &instruction),
+            Ok(())
+        );
+    }
+}
@@ -1,4 +1,20 @@
-use pinocchio::{log::sol_log, program_error::ProgramError, pubkey::Pubkey, ProgramResult};
+use borsh::{BorshDeserialize, BorshSerialize};
+use bytemuck::{AnyBitPattern, Pod};
+use pinocchio::{
+    account_info::{AccountInfo, Ref},
+    instruction::AccountMeta,
+    log::{sol_log, sol_log_data},
+    msg,
+    program_error::ProgramError,
+    pubkey::{self, Pubkey},
+    ProgramResult,
+};
+use pinocchio_token::state::Mint;
+
+use crate::{
+    AccountDiscriminator, AccountHeaderDeserialize, AccountInfoValidation, AsAccount, CloseAccount,
+    Discriminator, DiscriminatorWidth, HeaderWithCount, LamportTransfer, SysvarAccess, ToLogBytes,
+};
 
 /// Parses an instruction from the instruction data.
 pub fn parse_instruction<'a, T: std::convert::TryFrom<u8>>(
@@ -23,6 +39,296 @@ pub fn parse_instruction<'a, T: std::convert::TryFrom<u8>>(
     Ok((ix, data))
 }
 
+/// Encodes instruction data as the variant's discriminator followed by the borsh-serialized
+/// payload. This is the inverse of [`parse_instruction`].
+pub fn encode_instruction_data<E: Into<u8>, T: BorshSerialize>(
+    variant: E,
+    data: &T,
+) -> Result<Vec<u8>, ProgramError> {
+    let body = borsh::to_vec(data).map_err(|_| ProgramError::InvalidInstructionData)?;
+    let mut bytes = Vec::with_capacity(1 + body.len());
+    bytes.push(variant.into());
+    bytes.extend_from_slice(&body);
+    Ok(bytes)
+}
+
+/// Borrows the account data, failing if it is shorter than `min` bytes so it can be safely
+/// indexed.
+pub fn try_borrow_data_with_min_len(
+    info: &AccountInfo,
+    min: usize,
+) -> Result<Ref<'_, [u8]>, ProgramError> {
+    let data = info.try_borrow_data()?;
+    if data.len().lt(&min) {
+        msg!(
+            "Account data is too small (expected, actual): {:?}, {:?}",
+            min,
+            data.len()
+        );
+        pubkey::log(info.key());
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    Ok(data)
+}
+
+/// Writes `T`'s discriminator to the start of `buf`, e.g. when building account data by hand.
+/// Fails with `AccountDataTooSmall` if `buf` cannot hold it.
+pub fn write_discriminator<T: AccountDiscriminator>(buf: &mut [u8]) -> ProgramResult {
+    if buf.len().lt(&T::Width::LEN) {
+        msg!(
+            "Buffer is too small for discriminator (expected, actual): {:?}, {:?}",
+            T::Width::LEN,
+            buf.len()
+        );
+        return Err(ProgramError::AccountDataTooSmall);
+    }
+    T::account_discriminator().write(buf);
+    Ok(())
+}
+
+/// Builds an `AccountMeta` carrying the signer and writable flags of the provided account.
+#[inline(always)]
+pub fn to_account_meta(info: &AccountInfo) -> AccountMeta {
+    AccountMeta::new(info.key(), info.is_writable(), info.is_signer())
+}
+
+/// Builds an `AccountMeta` for each of the provided accounts, preserving their order.
+pub fn to_account_metas(infos: &[AccountInfo]) -> Vec<AccountMeta> {
+    infos.iter().map(to_account_meta).collect()
+}
+
+/// Validates that the account is a PDA of `seeds` using the bump stored in its own data, avoiding
+/// the cost of `find_program_address`.
+pub fn validate_stored_bump_pda<T, F>(
+    account: &AccountInfo,
+    seeds: &[&[u8]],
+    program_id: &Pubkey,
+    bump_field: F,
+) -> ProgramResult
+where
+    T: BorshDeserialize + Discriminator,
+    F: Fn(&T) -> u8,
+{
+    let bump = read_stored_bump(account, program_id, bump_field)?;
+    account.assert_seeds_with_provided_bump(seeds, bump, program_id)?;
+    Ok(())
+}
+
+/// Reads the bump stored in an account of type `T`, ignoring trailing space after the body.
+fn read_stored_bump<T, F>(
+    account: &AccountInfo,
+    program_id: &Pubkey,
+    bump_field: F,
+) -> Result<u8, ProgramError>
+where
+    T: BorshDeserialize + Discriminator,
+    F: Fn(&T) -> u8,
+{
+    account.assert_type::<T>(program_id)?;
+    let data = account.try_borrow_data()?;
+    let state = T::deserialize(&mut &data[1..]).map_err(|_| ProgramError::InvalidAccountData)?;
+    Ok(bump_field(&state))
+}
+
+/// Loads a batch of accounts of the same type via [`AsAccount::as_account`], returning the first
+/// error encountered.
+pub fn try_load_multiple<T, const N: usize>(
+    infos: &[AccountInfo; N],
+    program_id: &Pubkey,
+) -> Result<[T; N], ProgramError>
+where
+    T: BorshDeserialize + BorshSerialize + Discriminator,
+{
+    let mut accounts = Vec::with_capacity(N);
+    for info in infos {
+        accounts.push(info.as_account::<T>(program_id)?);
+    }
+    accounts
+        .try_into()
+        .map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// Sends the lamports `account` holds above the rent-exempt minimum for `data_len` bytes back to
+/// `payer`, returning the amount transferred. The account must be owned by the invoking program.
+pub fn transfer_excess_lamports(
+    account: &AccountInfo,
+    payer: &AccountInfo,
+    rent_sysvar: &AccountInfo,
+    data_len: usize,
+) -> Result<u64, ProgramError> {
+    let minimum_balance = rent_sysvar.as_rent()?.minimum_balance(data_len);
+    let excess = account.lamports().saturating_sub(minimum_balance);
+    if excess.gt(&0) {
+        account.send_safe(excess, payer)?;
+    }
+    Ok(excess)
+}
+
+/// Closes each of `accounts` into `destination`. All accounts are validated before any is closed,
+/// and since a failed instruction reverts all of its changes, accounts are never left partially
+/// closed.
+pub fn close_multiple(accounts: &[&AccountInfo], destination: &AccountInfo) -> ProgramResult {
+    for account in accounts {
+        account
+            .assert_writable()?
+            .assert_key_not(destination.key())?;
+    }
+    for account in accounts {
+        account.close(destination)?;
+    }
+    Ok(())
+}
+
+/// Byte offset of the body of `Pod` accounts: the discriminator byte padded to 8 bytes so the
+/// body stays aligned.
+pub const POD_BODY_OFFSET: usize = 8;
+
+/// Casts the `Pod` body of account data, failing with `InvalidAccountData` if the data is too
+/// short or misaligned for `T`. Does not check the discriminator.
+#[inline(always)]
+pub fn try_pod_body<T: AnyBitPattern>(data: &[u8]) -> Result<&T, ProgramError> {
+    let body = data
+        .get(POD_BODY_OFFSET..POD_BODY_OFFSET + std::mem::size_of::<T>())
+        .ok_or(ProgramError::InvalidAccountData)?;
+    bytemuck::try_from_bytes::<T>(body).or(Err(ProgramError::InvalidAccountData))
+}
+
+/// Mutable variant of [`try_pod_body`].
+#[inline(always)]
+pub fn try_pod_body_mut<T: Pod>(data: &mut [u8]) -> Result<&mut T, ProgramError> {
+    let body = data
+        .get_mut(POD_BODY_OFFSET..POD_BODY_OFFSET + std::mem::size_of::<T>())
+        .ok_or(ProgramError::InvalidAccountData)?;
+    bytemuck::try_from_bytes_mut::<T>(body).or(Err(ProgramError::InvalidAccountData))
+}
+
+/// Validates that the account is a writable `T` owned by `program_id` and returns a mutable
+/// reference to its `Pod` body, allowing fields to be updated in place without a copy. Performs
+/// the same checks as [`AsAccount::as_account_mut`], but does not hold the data borrow.
+///
+/// # Safety
+///
+/// The returned reference is not tracked by the account's borrow state. While it is alive, the
+/// caller must not borrow the account data again (checked or unchecked, including through a
+/// duplicate `AccountInfo` for the same account), and must not reallocate or close the account.
+/// Calling this while the data is already borrowed fails with `AccountBorrowFailed`.
+pub unsafe fn try_as_pod_ref<'a, T: Pod + Discriminator>(
+    info: &'a AccountInfo,
+    program_id: &Pubkey,
+) -> Result<&'a mut T, ProgramError> {
+    let body: *mut T = &mut *info.as_account_mut::<T>(program_id)?;
+    // The borrow guard is released here, leaving the caller responsible for exclusivity.
+    Ok(&mut *body)
+}
+
+/// Parses a header followed by a body of `header.body_count()` elements of `B`, e.g. a merkle tree
+/// whose depth is stored in its header. Fails with `InvalidAccountData` if the data is too short
+/// or the body is misaligned for `B`.
+pub fn try_header_body_from_bytes<H, B>(data: &[u8]) -> Result<(&H, &[B]), ProgramError>
+where
+    H: AccountHeaderDeserialize + HeaderWithCount,
+    B: Pod,
+{
+    let (header, remainder) = H::try_header_from_bytes(data)?;
+    let body_len = header
+        .body_count()
+        .checked_mul(std::mem::size_of::<B>())
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let body = remainder
+        .get(..body_len)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let body = bytemuck::try_cast_slice::<u8, B>(body).or(Err(ProgramError::InvalidAccountData))?;
+    Ok((header, body))
+}
+
+/// Returns whether the given public key is a valid point on the Ed25519 curve. Keys on the curve
+/// have a corresponding private key, while PDAs are guaranteed to be off the curve.
+#[inline(always)]
+pub fn is_on_curve(pubkey: &Pubkey) -> bool {
+    #[cfg(target_os = "solana")]
+    {
+        const CURVE25519_EDWARDS: u64 = 0;
+        let mut result = 0u8;
+        unsafe {
+            pinocchio::syscalls::sol_curve_validate_point(
+                CURVE25519_EDWARDS,
+                pubkey.as_ptr(),
+                &mut result,
+            ) == 0
+        }
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        curve25519_dalek::edwards::CompressedEdwardsY(*pubkey)
+            .decompress()
+            .is_some()
+    }
+}
+
+/// Logs multiple events with a single `sol_log_data` call.
+pub fn log_many(events: &[&dyn ToLogBytes]) {
+    let data = events
+        .iter()
+        .map(|event| event.to_log_bytes())
+        .collect::<Vec<_>>();
+    let slices = data.iter().map(Vec::as_slice).collect::<Vec<_>>();
+    sol_log_data(&slices);
+}
+
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Encodes bytes as base58, the textual format of Solana addresses.
+pub fn to_base58(bytes: &[u8]) -> String {
+    // Base58 digits, least significant first.
+    let mut digits: Vec<u8> = Vec::with_capacity(bytes.len() * 138 / 100 + 1);
+    for &byte in bytes {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    // Each leading zero byte is encoded as a leading '1'.
+    let leading_zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+    let mut encoded = "1".repeat(leading_zeros);
+    encoded.extend(
+        digits
+            .iter()
+            .rev()
+            .map(|digit| BASE58_ALPHABET[*digit as usize] as char),
+    );
+    encoded
+}
+
+/// Formats a lamport amount in SOL, e.g. `1230000000` as `1.230000000 SOL`. Prefer displaying
+/// [`LamportsAsSol`] directly where a `String` is not needed.
+pub fn format_sol(lamports: u64) -> String {
+    LamportsAsSol(lamports).to_string()
+}
+
+/// Displays a lamport amount as SOL with all 9 decimals, e.g. `1.230000000 SOL`, without
+/// allocating: `msg!("Balance: {}", LamportsAsSol(info.lamports()))`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LamportsAsSol(pub u64);
+
+impl core::fmt::Display for LamportsAsSol {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{}.{:09} SOL",
+            self.0 / 1_000_000_000,
+            self.0 % 1_000_000_000
+        )
+    }
+}
+
 #[track_caller]
 #[inline(always)]
 pub fn assert(v: bool, err: impl Into<ProgramError>, msg: &str) -> ProgramResult {
@@ -34,3 +340,262 @@ pub fn assert(v: bool, err: impl Into<ProgramError>, msg: &str) -> ProgramResult
         Err(err.into())
     }
 }
+
+/// Asserts at least `count` accounts were provided, so they can be safely indexed.
+#[inline(always)]
+pub fn expect_accounts(accounts: &[AccountInfo], count: usize) -> ProgramResult {
+    if accounts.len().lt(&count) {
+        msg!(
+            "Not enough accounts (expected, actual): {:?}, {:?}",
+            count,
+            accounts.len()
+        );
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+    Ok(())
+}
+
+/// Iterates over the provided accounts, asserting each is of type `T` and owned by `program_id`.
+/// Each item carries the account's index so callers can report which account failed validation.
+pub fn iter_validated_accounts_with_index<'a, T: Discriminator>(
+    accounts: &'a [AccountInfo],
+    program_id: &'a Pubkey,
+) -> impl Iterator<Item = (usize, Result<&'a AccountInfo, ProgramError>)> {
+    accounts
+        .iter()
+        .enumerate()
+        .map(move |(index, account)| (index, account.assert_type::<T>(program_id)))
+}
+
+/// Heuristic classification of a mint based on its supply and decimals.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MintState {
+    /// Divisible token, or an indivisible token with no supply minted yet.
+    Fungible,
+    /// Indivisible token with a supply of exactly one.
+    NonFungible,
+    /// Indivisible token with a supply greater than one (e.g. editions).
+    SemiFungible,
+}
+
+/// Classifies a mint as fungible, non-fungible, or semi-fungible.
+pub fn classify_mint(mint: &Mint) -> MintState {
+    match (mint.decimals(), mint.supply()) {
+        (0, 1) => MintState::NonFungible,
+        (0, supply) if supply > 1 => MintState::SemiFungible,
+        _ => MintState::Fungible,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestAccount;
+    use bytemuck::Zeroable;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Pod, Zeroable)]
+    struct TreeHeader {
+        depth: u64,
+    }
+
+    impl Discriminator for TreeHeader {
+        fn discriminator() -> u8 {
+            1
+        }
+    }
+
+    impl AccountHeaderDeserialize for TreeHeader {}
+
+    impl HeaderWithCount for TreeHeader {
+        fn body_count(&self) -> usize {
+            self.depth as usize
+        }
+    }
+
+    #[test]
+    fn pod_body_rejects_short_and_misaligned_data() {
+        let mut words = [0u64, 42, 0];
+        let data = bytemuck::bytes_of_mut(&mut words);
+        assert_eq!(try_pod_body::<u64>(data), Ok(&42));
+        assert_eq!(
+            try_pod_body::<u64>(&data[..15]),
+            Err(ProgramError::InvalidAccountData)
+        );
+        assert_eq!(
+            try_pod_body::<u64>(&data[1..]),
+            Err(ProgramError::InvalidAccountData)
+        );
+
+        *try_pod_body_mut::<u64>(data).unwrap() = 7;
+        assert_eq!(words[1], 7);
+    }
+
+    #[repr(C)]
+    #[derive(Clone, Copy, Pod, Zeroable)]
+    struct Counter {
+        value: u64,
+    }
+
+    impl Discriminator for Counter {
+        fn discriminator() -> u8 {
+            2
+        }
+    }
+
+    #[test]
+    fn pod_ref_writes_through_to_account_data() {
+        let program_id = [7; 32];
+        let mut data = vec![Counter::discriminator(), 0, 0, 0, 0, 0, 0, 0];
+        data.extend_from_slice(&5u64.to_le_bytes());
+        let mut account = TestAccount::new([1; 32], program_id, 0, &data);
+        let info = account.info();
+
+        let counter = unsafe { try_as_pod_ref::<Counter>(&info, &program_id) }.unwrap();
+        counter.value = 6;
+
+        assert_eq!(info.try_borrow_data().unwrap()[8..], 6u64.to_le_bytes());
+    }
+
+    #[test]
+    fn pod_ref_fails_while_data_is_borrowed() {
+        let program_id = [7; 32];
+        let mut data = vec![Counter::discriminator(), 0, 0, 0, 0, 0, 0, 0];
+        data.extend_from_slice(&5u64.to_le_bytes());
+        let mut account = TestAccount::new([1; 32], program_id, 0, &data);
+        let info = account.info();
+
+        let _guard = info.try_borrow_data().unwrap();
+        assert_eq!(
+            unsafe { try_as_pod_ref::<Counter>(&info, &program_id) }.err(),
+            Some(ProgramError::AccountBorrowFailed)
+        );
+    }
+
+    #[test]
+    fn lamports_display_as_sol() {
+        assert_eq!(LamportsAsSol(1_230_000_000).to_string(), "1.230000000 SOL");
+        assert_eq!(LamportsAsSol(5).to_string(), "0.000000005 SOL");
+        assert_eq!(format_sol(2_000_000_000), "2.000000000 SOL");
+    }
+
+    fn tree_data(depth: u64) -> [u64; 5] {
+        let mut words = [0, depth, 10, 20, 30];
+        bytemuck::bytes_of_mut(&mut words)[0] = TreeHeader::discriminator();
+        words
+    }
+
+    #[test]
+    fn header_body_from_bytes_reads_body() {
+        let words = tree_data(3);
+        let (header, body) =
+            try_header_body_from_bytes::<TreeHeader, u64>(bytemuck::bytes_of(&words)).unwrap();
+        assert_eq!(header.depth, 3);
+        assert_eq!(body, &[10, 20, 30]);
+    }
+
+    #[test]
+    fn header_body_from_bytes_rejects_truncated_data() {
+        let words = tree_data(3);
+        let data = bytemuck::bytes_of(&words);
+        for len in [0, 1, 8, 12, 16, 39] {
+            assert_eq!(
+                try_header_body_from_bytes::<TreeHeader, u64>(&data[..len]).err(),
+                Some(ProgramError::InvalidAccountData)
+            );
+        }
+
+        let words = tree_data(4);
+        assert_eq!(
+            try_header_body_from_bytes::<TreeHeader, u64>(bytemuck::bytes_of(&words)).err(),
+            Some(ProgramError::InvalidAccountData)
+        );
+    }
+
+    fn mint_data(supply: u64, decimals: u8) -> [u8; Mint::LEN] {
+        let mut data = [0; Mint::LEN];
+        data[36..44].copy_from_slice(&supply.to_le_bytes());
+        data[44] = decimals;
+        data[45] = 1;
+        data
+    }
+
+    #[test]
+    fn classify_mint_by_supply_and_decimals() {
+        for (supply, decimals, state) in [
+            (0, 0, MintState::Fungible),
+            (1, 0, MintState::NonFungible),
+            (5, 0, MintState::SemiFungible),
+            (0, 6, MintState::Fungible),
+            (1, 6, MintState::Fungible),
+            (5, 6, MintState::Fungible),
+        ] {
+            let data = mint_data(supply, decimals);
+            let mint = unsafe { Mint::from_bytes(&data) };
+            assert_eq!(
+                classify_mint(mint),
+                state,
+                "supply {supply}, decimals {decimals}"
+            );
+        }
+    }
+
+    #[test]
+    fn close_multiple_moves_all_lamports_to_destination() {
+        let mut first = TestAccount::new([1; 32], [7; 32], 10, &[1, 2]);
+        let mut second = TestAccount::new([2; 32], [7; 32], 20, &[3]);
+        let mut destination = TestAccount::new([3; 32], [0; 32], 5, &[]);
+        let (first, second, destination) = (first.info(), second.info(), destination.info());
+
+        close_multiple(&[&first, &second], &destination).unwrap();
+
+        assert_eq!(destination.lamports(), 35);
+        for closed in [&first, &second] {
+            assert_eq!(closed.lamports(), 0);
+            assert_eq!(closed.data_len(), 0);
+        }
+    }
+
+    #[test]
+    fn close_multiple_rejects_destination_before_closing_any() {
+        let mut first = TestAccount::new([1; 32], [7; 32], 10, &[1]);
+        let mut destination = TestAccount::new([3; 32], [7; 32], 5, &[]);
+        let (first, destination) = (first.info(), destination.info());
+
+        assert!(close_multiple(&[&first, &destination], &destination).is_err());
+        assert_eq!(first.lamports(), 10);
+        assert_eq!(first.data_len(), 1);
+    }
+
+    #[derive(BorshSerialize, BorshDeserialize)]
+    struct Vault {
+        authority: Pubkey,
+        bump: u8,
+    }
+
+    impl Discriminator for Vault {
+        fn discriminator() -> u8 {
+            4
+        }
+    }
+
+    #[test]
+    fn read_stored_bump_ignores_trailing_space() {
+        let program_id = [7; 32];
+        let mut data = vec![Vault::discriminator()];
+        data.extend(
+            Vault {
+                authority: [1; 32],
+                bump: 254,
+            }
+            .try_to_vec()
+            .unwrap(),
+        );
+        data.extend([0; 32]);
+        let mut account = TestAccount::new([2; 32], program_id, 0, &data);
+
+        let bump = read_stored_bump(&account.info(), &program_id, |vault: &Vault| vault.bump);
+
+        assert_eq!(bump, Ok(254));
+    }
+}